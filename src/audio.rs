@@ -1,13 +1,89 @@
-use bevy::prelude::*;
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Music>();
     app.register_type::<SoundEffect>();
+    app.register_type::<UiSound>();
 
-    app.add_systems(
-        Update,
-        apply_global_volume.run_if(resource_changed::<GlobalVolume>),
-    );
+    app.register_type::<ChannelVolumes>();
+    app.init_resource::<ChannelVolumes>();
+
+    app.add_systems(Update, apply_volume);
+}
+
+/// The audio categories that have their own volume level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum AudioChannel {
+    Music,
+    SoundEffects,
+    Ui,
+}
+
+impl AudioChannel {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Music => "Music Volume",
+            Self::SoundEffects => "SFX Volume",
+            Self::Ui => "UI Volume",
+        }
+    }
+}
+
+/// Volume level of a single [`AudioChannel`], applied on top of [`GlobalVolume`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelVolume {
+    /// Linear volume from 0 (silent) upwards.
+    pub level: f32,
+    pub muted: bool,
+}
+
+impl Default for ChannelVolume {
+    fn default() -> Self {
+        Self {
+            level: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl ChannelVolume {
+    fn volume(self) -> Volume {
+        if self.muted {
+            Volume::SILENT
+        } else {
+            Volume::Linear(self.level)
+        }
+    }
+}
+
+/// Volume levels for each [`AudioChannel`]. Set from [`Settings`](crate::settings::Settings).
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct ChannelVolumes {
+    pub music: ChannelVolume,
+    pub sound_effects: ChannelVolume,
+    pub ui: ChannelVolume,
+}
+
+impl ChannelVolumes {
+    pub fn get(&self, channel: AudioChannel) -> ChannelVolume {
+        match channel {
+            AudioChannel::Music => self.music,
+            AudioChannel::SoundEffects => self.sound_effects,
+            AudioChannel::Ui => self.ui,
+        }
+    }
+
+    pub fn get_mut(&mut self, channel: AudioChannel) -> &mut ChannelVolume {
+        match channel {
+            AudioChannel::Music => &mut self.music,
+            AudioChannel::SoundEffects => &mut self.sound_effects,
+            AudioChannel::Ui => &mut self.ui,
+        }
+    }
 }
 
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
@@ -36,12 +112,39 @@ pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
 }
 
-/// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
-fn apply_global_volume(
+/// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
+/// "UI" category (e.g. button hover and click sounds).
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct UiSound;
+
+/// A UI sound effect audio instance.
+pub fn ui_sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
+    (AudioPlayer(handle), PlaybackSettings::DESPAWN, UiSound)
+}
+
+/// [`GlobalVolume`] doesn't apply to already-running audio entities and Bevy doesn't know about
+/// [`ChannelVolumes`], so this system sets the volume of new sinks, and of every sink when either
+/// volume changes.
+fn apply_volume(
     global_volume: Res<GlobalVolume>,
-    mut audio_query: Query<(&PlaybackSettings, &mut AudioSink)>,
+    channel_volumes: Res<ChannelVolumes>,
+    mut audio_query: Query<(&PlaybackSettings, &mut AudioSink, Has<Music>, Has<UiSound>)>,
 ) {
-    for (playback, mut sink) in &mut audio_query {
-        sink.set_volume(global_volume.volume * playback.volume);
+    let volume_changed = global_volume.is_changed() || channel_volumes.is_changed();
+    for (playback, mut sink, music, ui) in &mut audio_query {
+        if !volume_changed && !sink.is_added() {
+            continue;
+        }
+
+        let channel = if music {
+            AudioChannel::Music
+        } else if ui {
+            AudioChannel::Ui
+        } else {
+            AudioChannel::SoundEffects
+        };
+        let channel_volume = channel_volumes.get(channel).volume();
+        sink.set_volume(global_volume.volume * channel_volume * playback.volume);
     }
 }
@@ -1,7 +1,55 @@
+use avian2d::prelude::*;
 use bevy::ecs::{query::QuerySingleError, system::SystemParam};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
+use crate::{AppSystems, PausableSystems};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FollowCamera>();
+    app.register_type::<CameraTarget>();
+
+    app.add_systems(
+        Update,
+        follow_camera_target
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Makes a 2D camera follow the [`CameraTarget`], looking ahead in the direction it's moving.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct FollowCamera {
+    /// How quickly the camera catches up with its target. Higher is snappier.
+    pub decay_rate: f32,
+    /// Seconds of the target's velocity to look ahead by.
+    pub look_ahead: f32,
+    /// The furthest the camera looks ahead of its target, in world units.
+    pub max_look_ahead: f32,
+    /// Orthographic scale while the target is still. Above 1 zooms out.
+    pub zoom: f32,
+    /// Extra scale per unit of the target's speed, so fast ships see further. 0 disables it.
+    pub speed_zoom: f32,
+}
+
+impl Default for FollowCamera {
+    fn default() -> Self {
+        Self {
+            decay_rate: 4.0,
+            look_ahead: 0.5,
+            max_look_ahead: 200.0,
+            zoom: 1.0,
+            speed_zoom: 0.0,
+        }
+    }
+}
+
+/// The entity a [`FollowCamera`] follows.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct CameraTarget;
+
 #[derive(SystemParam)]
 pub struct CursorPositionQuery<'w, 's> {
     window: Single<'w, 's, &'static Window, With<PrimaryWindow>>,
@@ -34,3 +82,30 @@ impl CursorPositionQuery<'_, '_> {
         Ok(world_position)
     }
 }
+
+fn follow_camera_target(
+    time: Res<Time>,
+    target: Single<
+        (&Transform, Option<&LinearVelocity>),
+        (With<CameraTarget>, Without<FollowCamera>),
+    >,
+    camera: Single<(&FollowCamera, &mut Transform, &mut Projection)>,
+) {
+    let (target_transform, velocity) = *target;
+    let (follow, mut transform, mut projection) = camera.into_inner();
+    let velocity = velocity.map_or(Vec2::ZERO, |velocity| velocity.0);
+    let delta = time.delta_secs();
+
+    let look_ahead = (velocity * follow.look_ahead).clamp_length_max(follow.max_look_ahead);
+    let goal = target_transform.translation.xy() + look_ahead;
+    let mut position = transform.translation.xy();
+    position.smooth_nudge(&goal, follow.decay_rate, delta);
+    transform.translation = position.extend(transform.translation.z);
+
+    if let Projection::Orthographic(orthographic) = &mut *projection {
+        let scale = follow.zoom + follow.speed_zoom * velocity.length();
+        orthographic
+            .scale
+            .smooth_nudge(&scale, follow.decay_rate, delta);
+    }
+}
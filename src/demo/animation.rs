@@ -11,11 +11,9 @@ use crate::{
         movement::MovementController,
         player::{PlayerAssets, PlayerShipEngineEffect},
     },
+    input::{Action, action_just_pressed, action_just_released},
 };
-use bevy::{
-    input::common_conditions::{input_just_pressed, input_just_released},
-    prelude::*,
-};
+use bevy::prelude::*;
 use rand::prelude::*;
 use std::time::Duration;
 
@@ -36,8 +34,10 @@ pub(super) fn plugin(app: &mut App) {
                 .run_if(resource_exists::<PlayerAssets>)
                 .in_set(AppSystems::Update),
             (
-                start_animation::<PlayerShipEngineEffect>.run_if(input_just_pressed(KeyCode::KeyW)),
-                stop_animation::<PlayerShipEngineEffect>.run_if(input_just_released(KeyCode::KeyW)),
+                start_animation::<PlayerShipEngineEffect>
+                    .run_if(action_just_pressed(Action::Thrust)),
+                stop_animation::<PlayerShipEngineEffect>
+                    .run_if(action_just_released(Action::Thrust)),
             )
                 .chain()
                 .in_set(AppSystems::Update),
@@ -199,7 +199,12 @@ impl PlayerAnimation {
 #[derive(Component)]
 struct AnimationPlaying;
 
-#[derive(Component)]
+/// An atlas animation that starts playing right away and loops forever.
+pub fn looping_animation(indices: AnimationIndices, fps: f32) -> impl Bundle {
+    (indices, AnimationTimer::with_fps(fps), AnimationPlaying)
+}
+
+#[derive(Component, Debug, Clone, Copy)]
 pub struct AnimationIndices {
     pub first: usize,
     pub last: usize,
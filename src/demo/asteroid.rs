@@ -0,0 +1,241 @@
+//! Asteroids that drift through the level and split apart when shot.
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        combat::{ContactDamage, DamageDealt, GameLayer, Health},
+        level::LevelConfig,
+        movement::WorldBound,
+        shapes::ShapeAssets,
+    },
+    screens::Screen,
+};
+use avian2d::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+use rand::prelude::*;
+
+/// How many smaller asteroids a destroyed asteroid breaks into.
+const FRAGMENT_COUNT: usize = 2;
+/// Extra speed given to fragments on top of the velocity of their parent.
+const FRAGMENT_SPLIT_SPEED: f32 = 60.0;
+const MAX_SPIN_SPEED: f32 = 1.5;
+pub const ASTEROID_COLOR: Color = Color::srgb(0.55, 0.5, 0.45);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Asteroid>();
+    app.add_message::<AsteroidDestroyed>();
+
+    app.add_systems(
+        Update,
+        split_destroyed_asteroids
+            .run_if(resource_exists::<ShapeAssets>)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// The size class of an [`Asteroid`]. Larger asteroids split into smaller ones when destroyed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum AsteroidSize {
+    Large,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    pub const ALL: [Self; 3] = [Self::Large, Self::Medium, Self::Small];
+
+    /// The size of the fragments this asteroid breaks into, or `None` if it is destroyed outright.
+    pub fn split(self) -> Option<Self> {
+        match self {
+            Self::Large => Some(Self::Medium),
+            Self::Medium => Some(Self::Small),
+            Self::Small => None,
+        }
+    }
+
+    /// How many projectile hits an asteroid of this size can take.
+    pub fn hit_points(self) -> f32 {
+        match self {
+            Self::Large => 3.0,
+            Self::Medium => 2.0,
+            Self::Small => 1.0,
+        }
+    }
+
+    /// Damage dealt to a ship that runs into an asteroid of this size.
+    pub fn contact_damage(self) -> f32 {
+        match self {
+            Self::Large => 4.0,
+            Self::Medium => 3.0,
+            Self::Small => 2.0,
+        }
+    }
+
+    /// Points awarded for destroying an asteroid of this size. Smaller ones are harder to hit.
+    pub fn score(self) -> u32 {
+        match self {
+            Self::Large => 20,
+            Self::Medium => 50,
+            Self::Small => 100,
+        }
+    }
+}
+
+/// How often a single [`AsteroidSize`] appears in a sector's belts, and how big it is.
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct AsteroidSizeConfig {
+    /// Relative chance of a belt asteroid being this size.
+    pub weight: f32,
+    /// Collider radius in world units.
+    pub radius: f32,
+}
+
+/// The asteroids that make up a level.
+#[derive(Debug, Clone, Reflect)]
+pub struct AsteroidField {
+    pub large: AsteroidSizeConfig,
+    pub medium: AsteroidSizeConfig,
+    pub small: AsteroidSizeConfig,
+    /// Minimum drift speed of a freshly spawned asteroid.
+    pub min_speed: f32,
+    /// Maximum drift speed of a freshly spawned asteroid.
+    pub max_speed: f32,
+    /// Asteroids won't spawn closer than this to the level origin, where the player starts.
+    pub clear_radius: f32,
+}
+
+impl Default for AsteroidField {
+    fn default() -> Self {
+        Self {
+            large: AsteroidSizeConfig {
+                weight: 4.0,
+                radius: 48.0,
+            },
+            medium: AsteroidSizeConfig {
+                weight: 2.0,
+                radius: 28.0,
+            },
+            small: AsteroidSizeConfig {
+                weight: 1.0,
+                radius: 14.0,
+            },
+            min_speed: 20.0,
+            max_speed: 80.0,
+            clear_radius: 200.0,
+        }
+    }
+}
+
+impl AsteroidField {
+    pub fn size_config(&self, size: AsteroidSize) -> AsteroidSizeConfig {
+        match size {
+            AsteroidSize::Large => self.large,
+            AsteroidSize::Medium => self.medium,
+            AsteroidSize::Small => self.small,
+        }
+    }
+
+    pub fn radius(&self, size: AsteroidSize) -> f32 {
+        self.size_config(size).radius
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+#[require(Visibility, RigidBody::Dynamic, WorldBound)]
+pub struct Asteroid {
+    pub size: AsteroidSize,
+}
+
+/// Sent when an asteroid runs out of health, just before it is despawned.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct AsteroidDestroyed {
+    pub size: AsteroidSize,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    /// The entity that dealt the final blow, if the asteroid was destroyed by damage from one.
+    pub destroyed_by: Option<Entity>,
+}
+
+/// An asteroid drifting with the given velocity.
+pub fn asteroid(
+    size: AsteroidSize,
+    radius: f32,
+    position: Vec2,
+    velocity: Vec2,
+    shape_assets: &ShapeAssets,
+) -> impl Bundle {
+    let spin = rand::rng().random_range(-MAX_SPIN_SPEED..=MAX_SPIN_SPEED);
+
+    (
+        Name::new(format!("{size:?} Asteroid")),
+        Asteroid { size },
+        Health::new(size.hit_points()),
+        ContactDamage(size.contact_damage()),
+        DespawnOnExit(Screen::Gameplay),
+        Collider::circle(radius),
+        CollisionLayers::new(GameLayer::Asteroid, LayerMask::ALL),
+        LinearVelocity(velocity),
+        AngularVelocity(spin),
+        Sprite {
+            image: shape_assets.circle.clone(),
+            color: ASTEROID_COLOR,
+            custom_size: Some(Vec2::splat(radius * 2.0)),
+            ..default()
+        },
+        Transform::from_translation(position.extend(0.0)),
+    )
+}
+
+/// Break apart asteroids that have run out of health.
+fn split_destroyed_asteroids(
+    mut commands: Commands,
+    asteroids: Query<(Entity, &Asteroid, &Health, &Transform, &LinearVelocity), Changed<Health>>,
+    level_config: Res<LevelConfig>,
+    shape_assets: Res<ShapeAssets>,
+    mut damage_dealt: MessageReader<DamageDealt>,
+    mut asteroid_destroyed: MessageWriter<AsteroidDestroyed>,
+) {
+    let rng = &mut rand::rng();
+    // the last hit on each asteroid is the one that destroyed it
+    let last_hit_by: HashMap<Entity, Entity> = damage_dealt
+        .read()
+        .map(|hit| (hit.target, hit.source))
+        .collect();
+
+    for (entity, rock, health, transform, velocity) in &asteroids {
+        if !health.is_dead() {
+            continue;
+        }
+        commands.entity(entity).despawn();
+        asteroid_destroyed.write(AsteroidDestroyed {
+            size: rock.size,
+            position: transform.translation.xy(),
+            velocity: velocity.0,
+            destroyed_by: last_hit_by.get(&entity).copied(),
+        });
+
+        let Some(fragment_size) = rock.size.split() else {
+            continue; // smallest asteroids are destroyed outright
+        };
+
+        // send fragments off in evenly spaced directions, starting from a random angle
+        let radius = level_config.asteroid_field.radius(fragment_size);
+        let start_angle = rng.random_range(0.0..std::f32::consts::TAU);
+        for i in 0..FRAGMENT_COUNT {
+            let angle = start_angle + std::f32::consts::TAU * i as f32 / FRAGMENT_COUNT as f32;
+            let direction = Vec2::from_angle(angle);
+            let position = transform.translation.xy() + direction * radius;
+            let fragment_velocity = velocity.0 + direction * FRAGMENT_SPLIT_SPEED;
+
+            commands.spawn(asteroid(
+                fragment_size,
+                radius,
+                position,
+                fragment_velocity,
+                &shape_assets,
+            ));
+        }
+    }
+}
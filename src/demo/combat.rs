@@ -0,0 +1,360 @@
+//! Health, damage and the collision layers that decide what can hit what.
+
+use super::weapon::Projectile;
+use crate::{AppSystems, PausableSystems, settings::Settings};
+use avian2d::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
+
+const HIT_FLASH_SECONDS: f32 = 0.08;
+const HIT_FLASH_COLOR: Color = Color::WHITE;
+/// How many times per second an invulnerable entity blinks.
+const INVULNERABLE_BLINK_RATE: f32 = 8.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Health>();
+    app.register_type::<Shield>();
+    app.register_type::<ContactDamage>();
+
+    app.add_message::<DamageDealt>();
+    app.add_message::<Detonation>().add_systems(
+        Update,
+        (
+            (tick_hit_flash, recharge_shields, tick_invulnerability).in_set(AppSystems::TickTimers),
+            (
+                apply_projectile_hits,
+                apply_contact_damage,
+                start_hit_flash,
+                end_hit_flash,
+            )
+                .chain()
+                .in_set(AppSystems::Update),
+        )
+            .in_set(PausableSystems),
+    );
+}
+
+/// The physics layers used to filter collisions.
+#[derive(PhysicsLayer, Debug, Clone, Copy, Default)]
+pub enum GameLayer {
+    #[default]
+    Default,
+    Player,
+    Enemy,
+    Asteroid,
+    Projectile,
+}
+
+impl GameLayer {
+    /// Layers for a projectile fired by a shooter with the given layers.
+    /// Projectiles never collide with each other or with anything sharing a layer with their shooter.
+    pub fn projectile_layers(shooter: Option<&CollisionLayers>) -> CollisionLayers {
+        let mut filters = LayerMask::ALL & !LayerMask::from(GameLayer::Projectile);
+        if let Some(shooter) = shooter {
+            filters = filters & !shooter.memberships;
+        }
+        CollisionLayers::new(GameLayer::Projectile, filters)
+    }
+}
+
+/// Hit points of anything that can be damaged. The entity is destroyed by its own module once
+/// [`Health::is_dead`] returns `true`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+    }
+}
+
+/// Absorbs damage before it reaches [`Health`], and recharges once the entity hasn't been hit for
+/// a while.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Shield {
+    pub current: f32,
+    pub max: f32,
+    /// Shield points restored per second while recharging.
+    pub recharge_rate: f32,
+    /// Time since the last hit before the shield starts recharging.
+    pub recharge_delay: Timer,
+}
+
+impl Shield {
+    pub fn new(max: f32, recharge_rate: f32, recharge_delay_secs: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            recharge_rate,
+            recharge_delay: Timer::from_seconds(recharge_delay_secs, TimerMode::Once),
+        }
+    }
+
+    /// Soak up as much of `amount` as the shield can, returning the damage that gets through.
+    pub fn absorb(&mut self, amount: f32) -> f32 {
+        self.recharge_delay.reset();
+        let absorbed = amount.min(self.current);
+        self.current -= absorbed;
+        amount - absorbed
+    }
+}
+
+/// Damage dealt to anything with [`Health`] that runs into this entity. Entities that deal contact
+/// damage don't hurt each other.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ContactDamage(pub f32);
+
+/// Ignores all damage and blinks until the timer runs out.
+#[derive(Component, Debug)]
+pub struct Invulnerable(pub Timer);
+
+impl Invulnerable {
+    pub fn from_seconds(seconds: f32) -> Self {
+        Self(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
+
+/// Sent whenever an entity with [`Health`] takes damage.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct DamageDealt {
+    /// The entity that was hit.
+    pub target: Entity,
+    /// The entity responsible for the damage, like the shooter of a projectile.
+    pub source: Entity,
+    /// The projectile that dealt the damage, if any. A blast hits everything in range with the
+    /// same projectile.
+    pub projectile: Option<Entity>,
+    /// How much health was removed.
+    pub amount: f32,
+}
+
+/// Sent when a projectile with a blast radius goes off.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct Detonation {
+    pub position: Vec2,
+    pub radius: f32,
+}
+
+/// Apply projectile damage to whatever they run into, despawning the projectile on impact.
+fn apply_projectile_hits(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    projectiles: Query<(&Projectile, &Transform)>,
+    mut targets: Query<
+        (Entity, &GlobalTransform, &mut Health, Option<&mut Shield>),
+        Without<Invulnerable>,
+    >,
+    mut damage_dealt: MessageWriter<DamageDealt>,
+    mut detonations: MessageWriter<Detonation>,
+) {
+    // a projectile can only hit one thing, even if it touches several in the same frame
+    let mut spent = HashSet::new();
+
+    for event in collisions.read() {
+        let (projectile_entity, target) = if projectiles.contains(event.collider1) {
+            (event.collider1, event.collider2)
+        } else if projectiles.contains(event.collider2) {
+            (event.collider2, event.collider1)
+        } else {
+            continue;
+        };
+
+        if !spent.insert(projectile_entity) {
+            continue;
+        }
+        commands.entity(projectile_entity).try_despawn();
+
+        let Ok((projectile, transform)) = projectiles.get(projectile_entity) else {
+            continue;
+        };
+
+        if let Some(radius) = projectile.blast_radius {
+            let position = transform.translation.xy();
+            detonations.write(Detonation { position, radius });
+
+            // everything caught in the blast takes the full damage, except the shooter
+            for (entity, target_transform, mut health, shield) in &mut targets {
+                let in_blast = target_transform.translation().xy().distance(position) <= radius;
+                if (entity != target && !in_blast)
+                    || entity == projectile.shooter
+                    || health.is_dead()
+                {
+                    continue;
+                }
+
+                deal_damage(&mut health, shield, projectile.damage);
+                damage_dealt.write(DamageDealt {
+                    target: entity,
+                    source: projectile.shooter,
+                    projectile: Some(projectile_entity),
+                    amount: projectile.damage,
+                });
+            }
+            continue;
+        }
+
+        let Ok((_, _, mut health, shield)) = targets.get_mut(target) else {
+            continue; // hit something indestructible
+        };
+        if health.is_dead() {
+            continue;
+        }
+
+        deal_damage(&mut health, shield, projectile.damage);
+        damage_dealt.write(DamageDealt {
+            target,
+            source: projectile.shooter,
+            projectile: Some(projectile_entity),
+            amount: projectile.damage,
+        });
+    }
+}
+
+/// Damage anything that runs into an entity with [`ContactDamage`].
+fn apply_contact_damage(
+    mut collisions: MessageReader<CollisionStart>,
+    dealers: Query<&ContactDamage>,
+    mut targets: Query<
+        (&mut Health, Option<&mut Shield>),
+        (Without<ContactDamage>, Without<Invulnerable>),
+    >,
+    mut damage_dealt: MessageWriter<DamageDealt>,
+) {
+    for event in collisions.read() {
+        for (dealer, target) in [
+            (event.collider1, event.collider2),
+            (event.collider2, event.collider1),
+        ] {
+            let Ok(contact_damage) = dealers.get(dealer) else {
+                continue;
+            };
+            let Ok((mut health, shield)) = targets.get_mut(target) else {
+                continue;
+            };
+            if health.is_dead() {
+                continue;
+            }
+
+            deal_damage(&mut health, shield, contact_damage.0);
+            damage_dealt.write(DamageDealt {
+                target,
+                source: dealer,
+                projectile: None,
+                amount: contact_damage.0,
+            });
+        }
+    }
+}
+
+/// Damage the shield first, if there is one, and the hull with whatever gets through.
+pub fn deal_damage(health: &mut Health, shield: Option<Mut<Shield>>, amount: f32) {
+    let hull_damage = shield.map_or(amount, |mut shield| shield.absorb(amount));
+    health.damage(hull_damage);
+}
+
+fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut query: Query<(Entity, &mut Invulnerable, &mut Visibility)>,
+) {
+    for (entity, mut invulnerable, mut visibility) in &mut query {
+        invulnerable.0.tick(time.delta());
+
+        if invulnerable.0.is_finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+            *visibility = Visibility::Inherited;
+        } else if !settings.accessibility.reduce_flashing {
+            let blink = (invulnerable.0.elapsed_secs() * INVULNERABLE_BLINK_RATE * 2.0) as u32;
+            *visibility = if blink % 2 == 0 {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+fn recharge_shields(time: Res<Time>, mut shields: Query<&mut Shield>) {
+    for mut shield in &mut shields {
+        // leave full shields alone so they don't show up as changed every frame
+        if shield.current >= shield.max {
+            continue;
+        }
+
+        shield.recharge_delay.tick(time.delta());
+        if shield.recharge_delay.is_finished() {
+            shield.current =
+                (shield.current + shield.recharge_rate * time.delta_secs()).min(shield.max);
+        }
+    }
+}
+
+/// Briefly tints a sprite after it takes damage.
+#[derive(Component, Debug)]
+struct HitFlash {
+    timer: Timer,
+    /// The sprite color to restore once the flash ends.
+    color: Color,
+}
+
+fn start_hit_flash(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut damage_dealt: MessageReader<DamageDealt>,
+    mut sprites: Query<(&mut Sprite, Option<&mut HitFlash>)>,
+) {
+    if settings.accessibility.reduce_flashing {
+        damage_dealt.clear();
+        return;
+    }
+
+    for hit in damage_dealt.read() {
+        if hit.amount <= 0.0 {
+            continue;
+        }
+        let Ok((mut sprite, flash)) = sprites.get_mut(hit.target) else {
+            continue;
+        };
+
+        match flash {
+            // already flashing, so keep the original color and start over
+            Some(mut flash) => flash.timer.reset(),
+            None => {
+                commands.entity(hit.target).try_insert(HitFlash {
+                    timer: Timer::from_seconds(HIT_FLASH_SECONDS, TimerMode::Once),
+                    color: sprite.color,
+                });
+            }
+        }
+        sprite.color = HIT_FLASH_COLOR;
+    }
+}
+
+fn tick_hit_flash(mut query: Query<&mut HitFlash>, time: Res<Time>) {
+    for mut flash in query.iter_mut() {
+        flash.timer.tick(time.delta());
+    }
+}
+
+fn end_hit_flash(mut commands: Commands, mut query: Query<(Entity, &HitFlash, &mut Sprite)>) {
+    for (entity, flash, mut sprite) in query.iter_mut() {
+        if flash.timer.is_finished() {
+            sprite.color = flash.color;
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
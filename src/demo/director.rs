@@ -0,0 +1,311 @@
+//! Paces a run by sending escalating waves of asteroids and enemies at the player, with a
+//! breather between each. How hard the waves hit depends on how well the player is doing.
+
+use bevy::{prelude::*, ui::Val::*};
+use rand::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        asteroid::{AsteroidSize, asteroid},
+        enemy::{Enemy, EnemySpawner, spawn_enemies},
+        level::LevelConfig,
+        player::Player,
+        respawn::Respawning,
+        sector::Sector,
+        shapes::ShapeAssets,
+    },
+    screens::Screen,
+    theme::prelude::*,
+};
+
+const ANNOUNCEMENT_SECONDS: f32 = 3.0;
+/// How far from their spawn point enemies can warp in, so they don't land on top of each other.
+const ENEMY_SPAWN_SCATTER: f32 = 60.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Director>();
+    app.init_resource::<Director>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), reset_director);
+    app.add_systems(
+        Update,
+        (
+            (tick_director, tick_announcements).in_set(AppSystems::TickTimers),
+            (
+                count_lives_lost,
+                advance_waves
+                    .run_if(resource_exists::<ShapeAssets>)
+                    .after(count_lives_lost),
+                despawn_finished_announcements,
+            )
+                .in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Tuning for the waves sent at the player during a level.
+///
+/// Spawn counts grow by a fixed amount each wave and are then multiplied by the director's
+/// [`Director::intensity`].
+#[derive(Debug, Clone, Reflect)]
+pub struct WaveConfig {
+    /// Seconds of calm before the first wave and after each wave ends.
+    pub breather_seconds: f32,
+    /// A wave ends when all of its enemies are destroyed, or after this many seconds.
+    pub max_wave_seconds: f32,
+    pub base_enemies: f32,
+    pub enemies_per_wave: f32,
+    /// Large asteroids that drift in from the edge of the level with each wave.
+    pub base_asteroids: f32,
+    pub asteroids_per_wave: f32,
+    /// How much the intensity rises after a wave cleared without losing a life, and falls for
+    /// each life lost during a wave.
+    pub intensity_step: f32,
+    pub min_intensity: f32,
+    pub max_intensity: f32,
+}
+
+impl Default for WaveConfig {
+    fn default() -> Self {
+        Self {
+            breather_seconds: 6.0,
+            max_wave_seconds: 60.0,
+            base_enemies: 1.0,
+            enemies_per_wave: 1.0,
+            base_asteroids: 1.0,
+            asteroids_per_wave: 0.5,
+            intensity_step: 0.15,
+            min_intensity: 0.5,
+            max_intensity: 2.0,
+        }
+    }
+}
+
+impl WaveConfig {
+    fn enemies(&self, wave: u32, intensity: f32) -> usize {
+        scaled_count(self.base_enemies, self.enemies_per_wave, wave, intensity)
+    }
+
+    fn asteroids(&self, wave: u32, intensity: f32) -> usize {
+        scaled_count(
+            self.base_asteroids,
+            self.asteroids_per_wave,
+            wave,
+            intensity,
+        )
+    }
+}
+
+fn scaled_count(base: f32, per_wave: f32, wave: u32, intensity: f32) -> usize {
+    let count = (base + per_wave * wave.saturating_sub(1) as f32) * intensity;
+    count.round().max(0.0) as usize
+}
+
+/// The state of the wave director for the current run.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct Director {
+    /// The wave in progress, or the last one sent. 0 before the first wave.
+    pub wave: u32,
+    /// Multiplier on spawn counts, adjusted after each wave to how well the player did.
+    pub intensity: f32,
+    phase: WavePhase,
+    /// Lives the player has lost during the current wave.
+    lives_lost: u32,
+}
+
+#[derive(Debug, Clone, Reflect)]
+enum WavePhase {
+    Breather(Timer),
+    Wave(Timer),
+}
+
+impl Default for Director {
+    fn default() -> Self {
+        Self::new(&WaveConfig::default())
+    }
+}
+
+impl Director {
+    fn new(config: &WaveConfig) -> Self {
+        Self {
+            wave: 0,
+            intensity: 1.0,
+            phase: WavePhase::Breather(Timer::from_seconds(
+                config.breather_seconds,
+                TimerMode::Once,
+            )),
+            lives_lost: 0,
+        }
+    }
+}
+
+/// A message shown in the middle of the screen for a few seconds.
+#[derive(Component, Debug)]
+struct Announcement(Timer);
+
+fn announcement(text: impl Into<String>) -> impl Bundle {
+    (
+        Name::new("Announcement"),
+        Announcement(Timer::from_seconds(ANNOUNCEMENT_SECONDS, TimerMode::Once)),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            top: Percent(25.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        // Don't block picking events for the game or menus.
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        children![widget::header(text)],
+    )
+}
+
+fn reset_director(mut commands: Commands, level_config: Res<LevelConfig>) {
+    commands.insert_resource(Director::new(&level_config.waves));
+}
+
+fn tick_director(time: Res<Time>, mut director: ResMut<Director>) {
+    // ticking isn't worth flagging as a change, or the director would change every frame
+    match &mut director.bypass_change_detection().phase {
+        WavePhase::Breather(timer) | WavePhase::Wave(timer) => {
+            timer.tick(time.delta());
+        }
+    }
+}
+
+fn count_lives_lost(
+    died: Query<(), (With<Player>, Added<Respawning>)>,
+    mut director: ResMut<Director>,
+) {
+    let lost = died.iter().count() as u32;
+    if lost > 0 {
+        director.lives_lost += lost;
+    }
+}
+
+/// Send the next wave once the breather is over, and start a breather once the wave is cleared
+/// or has run out of time.
+fn advance_waves(
+    mut commands: Commands,
+    mut director: ResMut<Director>,
+    level_config: Res<LevelConfig>,
+    sector: Res<Sector>,
+    shape_assets: Res<ShapeAssets>,
+    enemy_spawner: EnemySpawner,
+    enemies: Query<(), With<Enemy>>,
+) {
+    // leave the director untouched until the phase is over, so it only changes between phases
+    let phase_over = match &director.phase {
+        WavePhase::Breather(timer) => timer.is_finished(),
+        WavePhase::Wave(timer) => timer.is_finished() || enemies.is_empty(),
+    };
+    if !phase_over {
+        return;
+    }
+
+    let config = &level_config.waves;
+    let director = &mut *director;
+
+    match &director.phase {
+        WavePhase::Breather(_) => {
+            director.wave += 1;
+            director.lives_lost = 0;
+
+            // the same sector always sends the same waves, as long as the intensity matches
+            let rng = &mut sector.wave_rng(director.wave);
+            let positions: Vec<_> = (0..config.enemies(director.wave, director.intensity))
+                .map(|i| {
+                    let scatter = Vec2::new(
+                        rng.random_range(-ENEMY_SPAWN_SCATTER..=ENEMY_SPAWN_SCATTER),
+                        rng.random_range(-ENEMY_SPAWN_SCATTER..=ENEMY_SPAWN_SCATTER),
+                    );
+                    sector.enemy_spawn(director.wave, i) + scatter
+                })
+                .collect();
+            spawn_enemies(&mut commands, &enemy_spawner, rng, positions);
+            spawn_incoming_asteroids(
+                &mut commands,
+                rng,
+                &level_config,
+                config.asteroids(director.wave, director.intensity),
+                &shape_assets,
+            );
+            commands.spawn(announcement(format!("Wave {}", director.wave)));
+
+            director.phase = WavePhase::Wave(Timer::from_seconds(
+                config.max_wave_seconds,
+                TimerMode::Once,
+            ));
+        }
+        WavePhase::Wave(_) => {
+            let cleared = enemies.is_empty();
+
+            // push harder on a player who cleared the wave cleanly, and ease off on one who
+            // is struggling
+            if director.lives_lost > 0 {
+                director.intensity -= config.intensity_step * director.lives_lost as f32;
+            } else if cleared {
+                director.intensity += config.intensity_step;
+            }
+            director.intensity = director
+                .intensity
+                .clamp(config.min_intensity, config.max_intensity);
+
+            if cleared {
+                commands.spawn(announcement("Wave cleared"));
+            }
+            director.phase = WavePhase::Breather(Timer::from_seconds(
+                config.breather_seconds,
+                TimerMode::Once,
+            ));
+        }
+    }
+}
+
+/// Send `count` large asteroids drifting in from the edge of the sector, roughly towards the
+/// middle.
+fn spawn_incoming_asteroids(
+    commands: &mut Commands,
+    rng: &mut impl Rng,
+    level_config: &LevelConfig,
+    count: usize,
+    shape_assets: &ShapeAssets,
+) {
+    let field = &level_config.asteroid_field;
+    let half_extent = level_config.sector.extent / 2.0;
+
+    for _ in 0..count {
+        let direction = Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU));
+        let position = direction * half_extent.min_element();
+        // aim somewhere near the middle, so asteroids don't all converge on the player
+        let heading = Vec2::from_angle(rng.random_range(-0.5..=0.5)).rotate(-direction);
+        let speed = rng.random_range(field.min_speed..=field.max_speed);
+
+        commands.spawn(asteroid(
+            AsteroidSize::Large,
+            field.radius(AsteroidSize::Large),
+            position,
+            heading * speed,
+            shape_assets,
+        ));
+    }
+}
+
+fn tick_announcements(time: Res<Time>, mut query: Query<&mut Announcement>) {
+    for mut announcement in &mut query {
+        announcement.0.tick(time.delta());
+    }
+}
+
+fn despawn_finished_announcements(mut commands: Commands, query: Query<(Entity, &Announcement)>) {
+    for (entity, announcement) in &query {
+        if announcement.0.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
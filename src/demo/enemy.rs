@@ -0,0 +1,369 @@
+//! Hostile drones that hunt the player. Each enemy type is loaded from an `.enemy.ron` file under
+//! `assets/enemies`, pairing a ship hull with the parameters of its steering behavior.
+
+use avian2d::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::LoadResource,
+    demo::{
+        animation::looping_animation,
+        asteroid::Asteroid,
+        combat::{GameLayer, Health, Shield},
+        explosion::explosion,
+        movement::{MovementController, RotationSpeed, ShipSpeed, WorldBound, turn_towards},
+        player::{Player, ShipAssets},
+        respawn::Respawning,
+        ship::{ShipDefinition, ship_sprites},
+        weapon::{FireWeapon, Weapon, WeaponDefinition},
+    },
+    screens::Screen,
+};
+
+const ENEMY_LINEAR_DAMPING: f32 = 0.6;
+const ENEMY_ANGULAR_DAMPING: f32 = 4.0;
+const ENEMY_SHIELD_RECHARGE_RATE: f32 = 0.5;
+const ENEMY_SHIELD_RECHARGE_DELAY: f32 = 4.0;
+const ENGINE_ANIMATION_FPS: f32 = 12.0;
+const ENEMY_EXPLOSION_SIZE: f32 = 120.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<EnemyDefinition>();
+    app.init_asset_loader::<EnemyDefinitionLoader>();
+
+    app.register_type::<EnemyAssets>();
+    app.load_resource::<EnemyAssets>();
+
+    app.register_type::<Enemy>();
+    app.add_message::<EnemyDestroyed>();
+
+    app.add_systems(
+        Update,
+        (
+            tick_strafe_timers.in_set(AppSystems::TickTimers),
+            (steer_enemies, fire_enemy_weapons).in_set(AppSystems::RecordInput),
+            destroy_enemies
+                .run_if(resource_exists::<ShipAssets>)
+                .in_set(AppSystems::Update),
+        )
+            .in_set(PausableSystems),
+    );
+}
+
+/// An enemy type: the ship it flies and how it behaves.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct EnemyDefinition {
+    pub name: String,
+    pub ship: Handle<ShipDefinition>,
+    pub behavior: SteeringBehavior,
+    /// Points awarded to the player for destroying one.
+    pub score: u32,
+}
+
+/// Tuning for how an enemy chases the player.
+///
+/// Enemies further away than [`Self::preferred_distance`] fly straight at the player. Once close
+/// enough they orbit at that distance, switching direction every [`Self::strafe_interval`] seconds,
+/// and fire whenever the player is in range and in front of them. Asteroids within
+/// [`Self::avoid_radius`] push them away.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Deserialize)]
+pub struct SteeringBehavior {
+    pub preferred_distance: f32,
+    /// How far from the preferred distance an enemy can drift before heading straight back.
+    pub distance_tolerance: f32,
+    /// Seconds between changes of orbit direction, or 0 to always orbit the same way.
+    #[serde(default)]
+    pub strafe_interval: f32,
+    pub avoid_radius: f32,
+    /// How strongly asteroid avoidance outweighs chasing the player.
+    pub avoid_weight: f32,
+    pub fire_range: f32,
+    /// Half-angle in degrees of the cone in front of the ship that the player must be in to fire.
+    pub fire_cone: f32,
+}
+
+/// The layout of an `.enemy.ron` file. Paths are relative to the `assets` directory.
+#[derive(Deserialize)]
+struct EnemyFile {
+    name: String,
+    /// Path to a `.ship.ron` file.
+    ship: String,
+    behavior: SteeringBehavior,
+    score: u32,
+}
+
+#[derive(Default)]
+struct EnemyDefinitionLoader;
+
+impl AssetLoader for EnemyDefinitionLoader {
+    type Asset = EnemyDefinition;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<EnemyDefinition, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: EnemyFile = ron::de::from_bytes(&bytes)?;
+
+        Ok(EnemyDefinition {
+            name: file.name,
+            ship: load_context.load(file.ship),
+            behavior: file.behavior,
+            score: file.score,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["enemy.ron"]
+    }
+}
+
+/// Every enemy type that can appear in a level.
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct EnemyAssets {
+    #[dependency]
+    pub kinds: Vec<Handle<EnemyDefinition>>,
+}
+
+impl FromWorld for EnemyAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            kinds: vec![
+                assets.load("enemies/drone.enemy.ron"),
+                assets.load("enemies/striker.enemy.ron"),
+            ],
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[require(Visibility, RigidBody::Dynamic, WorldBound)]
+pub struct Enemy {
+    /// Points awarded to the player for destroying this enemy.
+    pub score: u32,
+}
+
+/// Per-enemy steering state.
+#[derive(Component, Debug)]
+struct Steering {
+    behavior: SteeringBehavior,
+    /// 1 to orbit counter clockwise, -1 for clockwise.
+    orbit_direction: f32,
+    strafe_timer: Option<Timer>,
+}
+
+/// Sent when an enemy runs out of health, just before it is despawned.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct EnemyDestroyed {
+    pub score: u32,
+}
+
+/// Everything needed to build an enemy from its definition.
+#[derive(SystemParam)]
+pub struct EnemySpawner<'w> {
+    enemy_assets: Res<'w, EnemyAssets>,
+    enemies: Res<'w, Assets<EnemyDefinition>>,
+    ships: Res<'w, Assets<ShipDefinition>>,
+    weapons: Res<'w, Assets<WeaponDefinition>>,
+}
+
+impl EnemySpawner<'_> {
+    /// How many enemy types there are to choose from.
+    pub fn kinds(&self) -> usize {
+        self.enemy_assets.kinds.len()
+    }
+
+    /// An enemy of the given type at `position`, or `None` if its definition isn't loaded.
+    /// Anything random about the enemy comes from `rng`, so seeded waves play out the same.
+    pub fn enemy(&self, kind: usize, position: Vec2, rng: &mut impl Rng) -> Option<impl Bundle> {
+        let definition = self.enemies.get(self.enemy_assets.kinds.get(kind)?)?;
+        let ship = self.ships.get(&definition.ship)?;
+        let behavior = definition.behavior;
+
+        Some((
+            Name::new(definition.name.clone()),
+            Enemy {
+                score: definition.score,
+            },
+            Steering {
+                behavior,
+                orbit_direction: if rng.random() { 1.0 } else { -1.0 },
+                strafe_timer: (behavior.strafe_interval > 0.0)
+                    .then(|| Timer::from_seconds(behavior.strafe_interval, TimerMode::Repeating)),
+            },
+            MovementController {
+                absolute: true,
+                max_speed: ship.max_speed,
+                ..default()
+            },
+            Weapon::new(&ship.hardpoints, &self.weapons),
+            Health::new(ship.hull),
+            Shield::new(
+                ship.shield,
+                ENEMY_SHIELD_RECHARGE_RATE,
+                ENEMY_SHIELD_RECHARGE_DELAY,
+            ),
+            ShipSpeed(ship.thrust),
+            RotationSpeed(ship.turn_rate),
+            LinearDamping(ENEMY_LINEAR_DAMPING),
+            AngularDamping(ENEMY_ANGULAR_DAMPING),
+            ship.collider.collider(),
+            CollisionLayers::new(GameLayer::Enemy, LayerMask::ALL),
+            CollisionEventsEnabled,
+            DespawnOnExit(Screen::Gameplay),
+            Transform::from_translation(position.extend(1.0))
+                .with_scale(Vec2::splat(ship.scale).extend(1.0)),
+            ship_sprites(
+                ship,
+                looping_animation(ship.engine.indices, ENGINE_ANIMATION_FPS),
+            ),
+        ))
+    }
+}
+
+/// Spawn an enemy of a random type at each of `positions`.
+pub fn spawn_enemies(
+    commands: &mut Commands,
+    spawner: &EnemySpawner,
+    rng: &mut impl Rng,
+    positions: impl IntoIterator<Item = Vec2>,
+) {
+    if spawner.kinds() == 0 {
+        return;
+    }
+
+    for position in positions {
+        let kind = rng.random_range(0..spawner.kinds());
+
+        if let Some(enemy) = spawner.enemy(kind, position, rng) {
+            commands.spawn(enemy);
+        }
+    }
+}
+
+fn tick_strafe_timers(time: Res<Time>, mut query: Query<&mut Steering>) {
+    for mut steering in &mut query {
+        let Some(timer) = &mut steering.strafe_timer else {
+            continue;
+        };
+        if timer.tick(time.delta()).just_finished() {
+            steering.orbit_direction = -steering.orbit_direction;
+        }
+    }
+}
+
+/// Seek, orbit and strafe the player while keeping clear of asteroids.
+fn steer_enemies(
+    time: Res<Time>,
+    player: Option<Single<&Transform, (With<Player>, Without<Respawning>)>>,
+    asteroids: Query<&Transform, (With<Asteroid>, Without<Enemy>)>,
+    mut enemies: Query<
+        (
+            &Steering,
+            &Transform,
+            &RotationSpeed,
+            &mut MovementController,
+            &mut AngularVelocity,
+        ),
+        With<Enemy>,
+    >,
+) {
+    for (steering, transform, rotation_speed, mut controller, mut angular_velocity) in &mut enemies
+    {
+        let behavior = steering.behavior;
+        let position = transform.translation.xy();
+
+        // push away from nearby asteroids, harder the closer they are
+        let avoidance: Vec2 = asteroids
+            .iter()
+            .map(|asteroid| position - asteroid.translation.xy())
+            .filter(|away| away.length() < behavior.avoid_radius)
+            .map(|away| away.normalize_or_zero() * (1.0 - away.length() / behavior.avoid_radius))
+            .sum::<Vec2>()
+            * behavior.avoid_weight;
+
+        // with nobody to chase, just keep out of the way of asteroids
+        let Some(player) = &player else {
+            controller.intent = avoidance.clamp_length_max(1.0);
+            continue;
+        };
+
+        let to_player = player.translation.xy() - position;
+        let distance = to_player.length();
+        let toward = to_player.normalize_or_zero();
+        let pursuit = if distance > behavior.preferred_distance + behavior.distance_tolerance {
+            toward
+        } else {
+            // circle the player, easing back towards the preferred distance
+            let correction =
+                (distance - behavior.preferred_distance) / behavior.distance_tolerance.max(1.0);
+            toward.perp() * steering.orbit_direction + toward * correction
+        };
+        controller.intent = (pursuit.normalize_or_zero() + avoidance).clamp_length_max(1.0);
+
+        // keep the guns pointed at the player
+        if let Some(turn) = turn_towards(
+            transform,
+            player.translation.xy(),
+            rotation_speed.0,
+            time.delta_secs(),
+        ) {
+            angular_velocity.0 = turn;
+        }
+    }
+}
+
+/// Fire at the player whenever they are in range and in front of the enemy.
+fn fire_enemy_weapons(
+    player: Single<&Transform, (With<Player>, Without<Respawning>)>,
+    enemies: Query<(Entity, &Steering, &Transform), With<Enemy>>,
+    mut fire_weapon: MessageWriter<FireWeapon>,
+) {
+    for (entity, steering, transform) in &enemies {
+        let to_player = player.translation.xy() - transform.translation.xy();
+        let forward = (transform.rotation * Vec3::Y).xy();
+        let in_range = to_player.length() <= steering.behavior.fire_range;
+        let in_cone = forward.angle_to(to_player).abs() <= steering.behavior.fire_cone.to_radians();
+
+        if in_range && in_cone {
+            fire_weapon.write(FireWeapon { entity });
+        }
+    }
+}
+
+fn destroy_enemies(
+    mut commands: Commands,
+    ship_assets: Res<ShipAssets>,
+    enemies: Query<(Entity, &Enemy, &Health, &Transform), Changed<Health>>,
+    mut enemy_destroyed: MessageWriter<EnemyDestroyed>,
+) {
+    for (entity, enemy, health, transform) in &enemies {
+        if !health.is_dead() {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+        commands.entity(entity).despawn();
+        commands.spawn(explosion(
+            position,
+            ENEMY_EXPLOSION_SIZE,
+            ship_assets.projectile.clone(),
+        ));
+        enemy_destroyed.write(EnemyDestroyed { score: enemy.score });
+    }
+}
@@ -0,0 +1,82 @@
+//! A simple expanding, fading explosion effect.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{combat::Detonation, player::ShipAssets},
+    screens::Screen,
+};
+
+const EXPLOSION_SECONDS: f32 = 0.6;
+const EXPLOSION_COLOR: Color = Color::srgb(1.0, 0.647, 0.302);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            spawn_detonations.run_if(resource_exists::<ShipAssets>),
+            animate_explosions,
+        )
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component, Debug)]
+struct Explosion {
+    timer: Timer,
+}
+
+/// An explosion at `position` that grows to `size` world units across while fading out.
+pub fn explosion(position: Vec2, size: f32, image: Handle<Image>) -> impl Bundle {
+    (
+        Name::new("Explosion"),
+        Explosion {
+            timer: Timer::from_seconds(EXPLOSION_SECONDS, TimerMode::Once),
+        },
+        Sprite {
+            image,
+            color: EXPLOSION_COLOR,
+            custom_size: Some(Vec2::splat(size)),
+            ..default()
+        },
+        // draw on top of ships and asteroids
+        Transform::from_translation(position.extend(5.0)).with_scale(Vec3::splat(0.1)),
+        DespawnOnExit(Screen::Gameplay),
+    )
+}
+
+/// Show how far the blast of a torpedo or other explosive projectile reached.
+fn spawn_detonations(
+    mut commands: Commands,
+    ship_assets: Res<ShipAssets>,
+    mut detonations: MessageReader<Detonation>,
+) {
+    for detonation in detonations.read() {
+        commands.spawn(explosion(
+            detonation.position,
+            2.0 * detonation.radius,
+            ship_assets.projectile.clone(),
+        ));
+    }
+}
+
+fn animate_explosions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut explosions: Query<(Entity, &mut Explosion, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut explosion, mut transform, mut sprite) in &mut explosions {
+        explosion.timer.tick(time.delta());
+        if explosion.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = explosion.timer.fraction();
+        // ease out, so the blast is fast at first and then lingers
+        transform.scale = Vec3::splat(0.1 + 0.9 * (1.0 - (1.0 - progress).powi(2)));
+        sprite.color = EXPLOSION_COLOR.with_alpha(1.0 - progress);
+    }
+}
@@ -0,0 +1,230 @@
+//! The in-game HUD, showing the state of the player's ship.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    demo::{
+        combat::{Health, Shield},
+        director::Director,
+        ore::Cargo,
+        player::{Lives, Player},
+        score::Score,
+        station::Credits,
+        weapon::{Weapon, WeaponDefinition},
+    },
+    screens::Screen,
+    theme::{prelude::*, widget::ProgressBar},
+};
+
+const BAR_WIDTH: f32 = 200.0;
+const HULL_COLOR: Color = Color::srgb(0.850, 0.302, 0.255);
+const SHIELD_COLOR: Color = Color::srgb(0.329, 0.690, 0.918);
+const CARGO_COLOR: Color = Color::srgb(0.867, 0.690, 0.314);
+const COOLDOWN_COLOR: Color = Color::srgb(0.925, 0.925, 0.925);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<HudElement>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_hud);
+    app.add_systems(
+        Update,
+        (
+            update_hull,
+            update_shield,
+            update_cargo,
+            update_weapon_cooldown,
+            update_score,
+            update_lives,
+            update_wave.run_if(resource_changed::<Director>),
+            // credits don't change on entering gameplay, so fill in a freshly spawned HUD too
+            update_credits
+                .run_if(resource_changed::<Credits>.or(any_match_filter::<Added<HudElement>>)),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Identifies the HUD node that shows a piece of the player's state.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum HudElement {
+    Hull,
+    Shield,
+    Cargo,
+    CargoLabel,
+    WeaponLabel,
+    WeaponCooldown,
+    Score,
+    Lives,
+    Wave,
+    Credits,
+}
+
+fn spawn_hud(mut commands: Commands) {
+    commands.spawn((
+        Name::new("HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Start,
+            padding: UiRect::all(Px(16.0)),
+            ..default()
+        },
+        // Don't block picking events for the game or menus.
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        children![
+            (
+                Name::new("Ship Status"),
+                Node {
+                    display: Display::Grid,
+                    grid_template_columns: vec![GridTrack::auto(), GridTrack::px(BAR_WIDTH)],
+                    row_gap: Px(6.0),
+                    column_gap: Px(10.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                children![
+                    widget::label("Hull"),
+                    (
+                        widget::progress_bar(BAR_WIDTH, HULL_COLOR),
+                        HudElement::Hull
+                    ),
+                    widget::label("Shield"),
+                    (
+                        widget::progress_bar(BAR_WIDTH, SHIELD_COLOR),
+                        HudElement::Shield
+                    ),
+                    (widget::label("Cargo"), HudElement::CargoLabel),
+                    (
+                        widget::progress_bar(BAR_WIDTH, CARGO_COLOR),
+                        HudElement::Cargo
+                    ),
+                    (widget::label(""), HudElement::WeaponLabel),
+                    (
+                        widget::progress_bar(BAR_WIDTH, COOLDOWN_COLOR),
+                        HudElement::WeaponCooldown
+                    ),
+                ],
+            ),
+            (
+                Name::new("Run Status"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::End,
+                    row_gap: Px(6.0),
+                    ..default()
+                },
+                children![
+                    (widget::label(""), HudElement::Score),
+                    (widget::label(""), HudElement::Lives),
+                    (widget::label(""), HudElement::Wave),
+                    (widget::label(""), HudElement::Credits),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn set_bar(bars: &mut Query<(&HudElement, &mut ProgressBar)>, element: HudElement, fraction: f32) {
+    for (_, mut bar) in bars.iter_mut().filter(|(hud, _)| **hud == element) {
+        bar.set_if_neq(ProgressBar(fraction));
+    }
+}
+
+fn set_text(texts: &mut Query<(&HudElement, &mut Text)>, element: HudElement, text: String) {
+    for (_, mut label) in texts.iter_mut().filter(|(hud, _)| **hud == element) {
+        // the weapon label is updated whenever the cooldown ticks, so avoid needless change
+        // detection
+        if label.0 != text {
+            label.0.clone_from(&text);
+        }
+    }
+}
+
+fn update_hull(
+    health: Single<&Health, (With<Player>, Changed<Health>)>,
+    mut bars: Query<(&HudElement, &mut ProgressBar)>,
+) {
+    set_bar(&mut bars, HudElement::Hull, health.current / health.max);
+}
+
+fn update_shield(
+    shield: Single<&Shield, (With<Player>, Changed<Shield>)>,
+    mut bars: Query<(&HudElement, &mut ProgressBar)>,
+) {
+    set_bar(&mut bars, HudElement::Shield, shield.current / shield.max);
+}
+
+fn update_cargo(
+    cargo: Single<&Cargo, (With<Player>, Changed<Cargo>)>,
+    mut bars: Query<(&HudElement, &mut ProgressBar)>,
+    mut texts: Query<(&HudElement, &mut Text)>,
+) {
+    let fill = cargo.total() as f32 / cargo.capacity.max(1) as f32;
+    set_bar(&mut bars, HudElement::Cargo, fill);
+    set_text(
+        &mut texts,
+        HudElement::CargoLabel,
+        format!("Cargo {}/{}", cargo.total(), cargo.capacity),
+    );
+}
+
+/// The bar empties as the weapon comes off cooldown. The label shows the selected weapon and the
+/// ammo it has left.
+fn update_weapon_cooldown(
+    weapon: Single<&Weapon, (With<Player>, Changed<Weapon>)>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    mut bars: Query<(&HudElement, &mut ProgressBar)>,
+    mut texts: Query<(&HudElement, &mut Text)>,
+) {
+    set_bar(
+        &mut bars,
+        HudElement::WeaponCooldown,
+        weapon.fire_rate_timer.fraction_remaining(),
+    );
+
+    let Some(selected) = weapon.selected() else {
+        return;
+    };
+    let Some(definition) = definitions.get(&selected.definition) else {
+        return;
+    };
+    let label = match selected.ammo {
+        Some(ammo) => format!("{} {ammo}", definition.name),
+        None => definition.name.clone(),
+    };
+    set_text(&mut texts, HudElement::WeaponLabel, label);
+}
+
+fn update_score(
+    score: Single<&Score, (With<Player>, Changed<Score>)>,
+    mut texts: Query<(&HudElement, &mut Text)>,
+) {
+    set_text(&mut texts, HudElement::Score, format!("Score {}", score.0));
+}
+
+fn update_lives(
+    lives: Single<&Lives, (With<Player>, Changed<Lives>)>,
+    mut texts: Query<(&HudElement, &mut Text)>,
+) {
+    set_text(&mut texts, HudElement::Lives, format!("Lives {}", lives.0));
+}
+
+fn update_wave(director: Res<Director>, mut texts: Query<(&HudElement, &mut Text)>) {
+    let label = match director.wave {
+        0 => "Get ready".to_string(),
+        wave => format!("Wave {wave}"),
+    };
+    set_text(&mut texts, HudElement::Wave, label);
+}
+
+fn update_credits(credits: Res<Credits>, mut texts: Query<(&HudElement, &mut Text)>) {
+    set_text(
+        &mut texts,
+        HudElement::Credits,
+        format!("Credits {}", credits.0),
+    );
+}
@@ -1,19 +1,41 @@
 //! Spawn the main level.
 
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 
 use crate::{
     asset_tracking::LoadResource,
     audio::music,
-    demo::player::{PlayerAssets, ShipAssets, fighter_ship},
+    camera::FollowCamera,
+    demo::{
+        asteroid::{AsteroidField, asteroid},
+        director::WaveConfig,
+        movement::WorldEdges,
+        player::{PlayerAssets, player_ship},
+        sector::{Sector, SectorConfig, SectorSeed, hazard},
+        shapes::ShapeAssets,
+        ship::{HullAssets, SelectedShip, ShipDefinition},
+        station::station,
+        upgrades::Upgrades,
+        weapon::WeaponDefinition,
+    },
     screens::Screen,
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<LevelAssets>();
     app.load_resource::<LevelAssets>();
+
+    app.register_type::<LevelConfig>();
+    app.init_resource::<LevelConfig>();
+
+    app.add_systems(OnExit(Screen::Gameplay), reset_camera);
 }
 
+/// Width and height of the default level's world.
+const WORLD_SIZE: f32 = 4000.0;
+/// How far the station's center is kept from the edge of the world.
+const STATION_EDGE_MARGIN: f32 = 100.0;
+
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct LevelAssets {
@@ -30,14 +52,93 @@ impl FromWorld for LevelAssets {
     }
 }
 
+/// Tuning for the level that is spawned when gameplay starts.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct LevelConfig {
+    pub asteroid_field: AsteroidField,
+    pub sector: SectorConfig,
+    pub waves: WaveConfig,
+    pub world: WorldEdges,
+    /// How the camera follows the player. Unused with [`WorldEdges::ScreenWrap`], where the
+    /// camera stays put.
+    pub camera: FollowCamera,
+}
+
+impl Default for LevelConfig {
+    fn default() -> Self {
+        Self {
+            asteroid_field: AsteroidField::default(),
+            sector: SectorConfig {
+                extent: Vec2::splat(WORLD_SIZE),
+                min_belts: 3,
+                max_belts: 6,
+                min_asteroids_per_belt: 6,
+                max_asteroids_per_belt: 12,
+                max_hazards: 5,
+                min_hazard_radius: 120.0,
+                max_hazard_radius: 320.0,
+                enemy_spawn_points: 4,
+                min_station_distance: 600.0,
+                max_station_distance: 1200.0,
+                ..default()
+            },
+            waves: WaveConfig::default(),
+            world: WorldEdges::Bounded(Vec2::splat(WORLD_SIZE)),
+            camera: FollowCamera {
+                speed_zoom: 0.001,
+                ..default()
+            },
+        }
+    }
+}
+
+impl LevelConfig {
+    /// The sector config, shrunk to fit what the player can reach with the level's world edges.
+    /// With [`WorldEdges::ScreenWrap`] that's the window, so the same seed lays out a different
+    /// sector in a different sized window.
+    fn sector_config(&self, window_size: Vec2) -> SectorConfig {
+        let reachable = match self.world {
+            WorldEdges::ScreenWrap => window_size,
+            WorldEdges::Bounded(size) => size,
+            WorldEdges::Infinite => return self.sector.clone(),
+        };
+        let extent = self.sector.extent.min(reachable);
+        // keep the whole station inside the world
+        let max_station_distance = self
+            .sector
+            .max_station_distance
+            .min(extent.min_element() / 2.0 - STATION_EDGE_MARGIN)
+            .max(0.0);
+        SectorConfig {
+            extent,
+            min_station_distance: self.sector.min_station_distance.min(max_station_distance),
+            max_station_distance,
+            ..self.sector.clone()
+        }
+    }
+}
+
 /// A system that spawns the main level.
 pub fn spawn_level(
     mut commands: Commands,
     level_assets: Res<LevelAssets>,
+    level_config: Res<LevelConfig>,
     _player_assets: Res<PlayerAssets>,
-    ship_assets: Res<ShipAssets>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    hull_assets: Res<HullAssets>,
+    selected_ship: Res<SelectedShip>,
+    ship_definitions: Res<Assets<ShipDefinition>>,
+    weapon_definitions: Res<Assets<WeaponDefinition>>,
+    upgrades: Res<Upgrades>,
+    shape_assets: Res<ShapeAssets>,
+    sector_seed: Res<SectorSeed>,
+    camera: Single<Entity, With<Camera2d>>,
+    window: Single<&Window, With<PrimaryWindow>>,
 ) {
+    let Some(ship) = hull_assets.selected(*selected_ship, &ship_definitions) else {
+        return error!("the selected ship hasn't been loaded.");
+    };
+
     commands.spawn((
         Name::new("Level"),
         Transform::default(),
@@ -45,11 +146,80 @@ pub fn spawn_level(
         DespawnOnExit(Screen::Gameplay),
         children![
             // player(400.0, &player_assets, &mut texture_atlas_layouts),
-            fighter_ship(&ship_assets, &mut texture_atlas_layouts),
+            player_ship(ship, &upgrades.modifiers(), &weapon_definitions),
             (
                 Name::new("Gameplay Music"),
                 music(level_assets.music.clone())
             )
         ],
     ));
+
+    let sector = Sector::generate(
+        sector_seed.seed,
+        &level_config.sector_config(window.size()),
+        &level_config.asteroid_field,
+    );
+    info!("generated sector {}", sector.seed);
+
+    for spawn in &sector.asteroids {
+        commands.spawn(asteroid(
+            spawn.size,
+            level_config.asteroid_field.radius(spawn.size),
+            spawn.position,
+            spawn.velocity,
+            &shape_assets,
+        ));
+    }
+    for zone in &sector.hazards {
+        commands.spawn(hazard(*zone, &shape_assets));
+    }
+    commands.spawn(station(sector.station, &shape_assets));
+    commands.insert_resource(sector);
+
+    commands.insert_resource(level_config.world);
+    if level_config.world != WorldEdges::ScreenWrap {
+        commands.entity(*camera).insert(level_config.camera);
+    }
+}
+
+/// Put the camera back where the menus expect it.
+fn reset_camera(
+    mut commands: Commands,
+    camera: Single<(Entity, &mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    let (entity, mut transform, mut projection) = camera.into_inner();
+    commands.entity(entity).remove::<FollowCamera>();
+    *transform = Transform::default();
+    if let Projection::Orthographic(orthographic) = &mut *projection {
+        orthographic.scale = 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screen_wrap_fits_the_sector_in_the_window() {
+        let window_size = Vec2::new(1280.0, 720.0);
+        let config = LevelConfig {
+            world: WorldEdges::ScreenWrap,
+            ..default()
+        };
+        let sector = config.sector_config(window_size);
+        assert_eq!(sector.extent, window_size);
+        assert!(sector.min_station_distance <= sector.max_station_distance);
+        assert!(sector.max_station_distance + STATION_EDGE_MARGIN <= window_size.y / 2.0);
+    }
+
+    #[test]
+    fn bounded_world_keeps_the_configured_sector() {
+        let config = LevelConfig::default();
+        let sector = config.sector_config(Vec2::new(1280.0, 720.0));
+        assert_eq!(sector.extent, config.sector.extent);
+        assert_eq!(
+            sector.max_station_distance,
+            config.sector.max_station_distance
+        );
+    }
 }
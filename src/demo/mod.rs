@@ -6,17 +6,58 @@
 use bevy::prelude::*;
 
 mod animation;
+mod asteroid;
+mod combat;
+mod director;
+mod enemy;
+mod explosion;
+mod hud;
 pub mod level;
-mod movement;
+pub mod movement;
+mod ore;
 pub mod player;
-mod weapon;
+mod radar;
+mod respawn;
+pub mod save;
+mod score;
+pub mod sector;
+mod shapes;
+pub mod ship;
+mod starfield;
+pub mod station;
+pub mod stats;
+pub mod upgrades;
+pub mod weapon;
 
 pub(super) fn plugin(app: &mut App) {
+    // split in two, as `add_plugins` takes tuples of at most 15 plugins
     app.add_plugins((
-        animation::plugin,
-        level::plugin,
-        movement::plugin,
-        player::plugin,
-        weapon::plugin,
+        (
+            animation::plugin,
+            asteroid::plugin,
+            combat::plugin,
+            director::plugin,
+            enemy::plugin,
+            explosion::plugin,
+            hud::plugin,
+            level::plugin,
+            movement::plugin,
+            ore::plugin,
+        ),
+        (
+            player::plugin,
+            radar::plugin,
+            respawn::plugin,
+            save::plugin,
+            score::plugin,
+            sector::plugin,
+            shapes::plugin,
+            ship::plugin,
+            starfield::plugin,
+            station::plugin,
+            stats::plugin,
+            upgrades::plugin,
+            weapon::plugin,
+        ),
     ));
 }
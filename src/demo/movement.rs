@@ -6,37 +6,100 @@
 //! - Set [`MovementController`] intent based on directional keyboard input.
 //!   This is done in the `player` module, as it is specific to the player
 //!   character.
-//! - Apply movement based on [`MovementController`] intent and maximum speed.
-//! - Wrap the character within the window.
-//!
-//! Note that the implementation used here is limited for demonstration
-//! purposes. If you want to move the player in a smoother way,
-//! consider using a [fixed timestep](https://github.com/bevyengine/bevy/blob/main/examples/movement/physics_in_fixed_timestep.rs).
-
-use crate::{AppSystems, PausableSystems, camera::CursorPositionQuery, demo::player::Player};
+//! - Accelerate ships along their facing based on [`MovementController`] intent,
+//!   capped at the maximum speed. Drag comes from the physics engine's
+//!   [`LinearDamping`] and [`AngularDamping`].
+//! - Keep the character inside the world, wrapping or bouncing at its [`WorldEdges`].
+
+use crate::{
+    AppSystems, PausableSystems,
+    camera::CursorPositionQuery,
+    demo::{player::Player, respawn::Respawning},
+    input::{Action, ActionInput},
+};
 use avian2d::prelude::*;
 use bevy::{prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+/// How far ahead of the ship the right stick aims, in world units.
+const STICK_AIM_DISTANCE: f32 = 100.0;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<MovementController>();
-    app.register_type::<ScreenWrap>();
+    app.register_type::<WorldBound>();
+    app.register_type::<WorldEdges>();
+    app.init_resource::<WorldEdges>();
+
+    app.register_type::<ControlScheme>();
+    app.init_resource::<ControlScheme>();
 
     app.add_systems(
         Update,
-        (
-            apply_player_movement,
-            apply_player_rotation,
-            apply_screen_wrap,
-        )
+        (apply_thrust, apply_player_rotation, apply_world_edges)
             .chain()
             .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
 }
 
+/// Kept inside the world by its [`WorldEdges`].
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-pub struct ScreenWrap;
+pub struct WorldBound;
+
+/// What happens to [`WorldBound`] entities at the edge of the world. Set by the level.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum WorldEdges {
+    /// Wrap around to the other side of the window, which the camera never leaves.
+    #[default]
+    ScreenWrap,
+    /// Bounce off the edges of a world of the given size, centered on the origin.
+    Bounded(Vec2),
+    /// There are no edges.
+    Infinite,
+}
+
+/// How the player steers their ship. Set from [`Settings`](crate::settings::Settings).
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum ControlScheme {
+    /// Turn left and right with A/D, thrust along the ship's facing.
+    Tank,
+    /// Turn towards the cursor while aiming, thrust along the ship's facing.
+    #[default]
+    CursorFollow,
+    /// Always face the cursor, thrust in the direction of the movement keys.
+    TwinStick,
+}
+
+impl ControlScheme {
+    const ALL: [Self; 3] = [Self::Tank, Self::CursorFollow, Self::TwinStick];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tank => "Tank",
+            Self::CursorFollow => "Cursor Follow",
+            Self::TwinStick => "Twin-Stick",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|scheme| *scheme == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|scheme| *scheme == self)
+            .unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
 
 /// These are the movement parameters for our character controller.
 /// For now, this is only used for a single player, but it could power NPCs or
@@ -44,9 +107,13 @@ pub struct ScreenWrap;
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct MovementController {
-    /// The direction the character wants to move in.
+    /// How the character wants to move. For ships, `y` is the throttle (negative for reverse)
+    /// and `x` is the turn direction, unless [`Self::absolute`] is set.
     pub intent: Vec2,
 
+    /// Whether [`Self::intent`] is a world-space thrust direction instead of throttle and turn.
+    pub absolute: bool,
+
     /// Maximum speed in world units per second.
     /// 1 world unit = 1 pixel when using the default 2D camera and no physics engine.
     pub max_speed: f32,
@@ -56,163 +123,176 @@ impl Default for MovementController {
     fn default() -> Self {
         Self {
             intent: Vec2::ZERO,
+            absolute: false,
             // 400 pixels per second is a nice default, but we can still vary this per character.
             max_speed: 400.0,
         }
     }
 }
 
+/// Forward thrust of a ship, as an acceleration in world units per second squared.
 #[derive(Component)]
 pub struct ShipSpeed(pub f32);
 
+/// Maximum turn rate of a ship in radians per second.
 #[derive(Component, Debug)]
 pub struct RotationSpeed(pub f32);
 
-// TODO: update player movement to be closer to this
-fn _apply_movement(
+/// Lets a ship thrust backwards. The value is the fraction of [`ShipSpeed`] available in reverse.
+/// Ships without this component can only slow down by turning around or through damping.
+#[derive(Component, Debug)]
+pub struct ReverseThrusters(pub f32);
+
+/// Accelerate ships based on their [`MovementController::intent`].
+/// Velocity carries over between frames, so ships keep drifting once thrust is released.
+fn apply_thrust(
     time: Res<Time>,
-    mut movement_query: Query<(&MovementController, &mut Transform)>,
+    mut query: Query<(
+        &MovementController,
+        &Transform,
+        &ShipSpeed,
+        Option<&ReverseThrusters>,
+        &mut LinearVelocity,
+    )>,
 ) {
-    for (controller, mut transform) in &mut movement_query {
-        let velocity = controller.max_speed * controller.intent;
-        transform.translation += velocity.extend(0.0) * time.delta_secs();
+    for (controller, transform, ship_speed, reverse_thrusters, mut linear_velocity) in
+        query.iter_mut()
+    {
+        let acceleration = if controller.absolute {
+            controller.intent.clamp_length_max(1.0) * ship_speed.0
+        } else {
+            let mut throttle = controller.intent.y.clamp(-1.0, 1.0);
+            if throttle < 0.0 {
+                throttle *= reverse_thrusters.map_or(0.0, |reverse| reverse.0);
+            }
+
+            // get the ship's forward vector by applying the current rotation to the ships initial
+            // facing vector
+            let forward = (transform.rotation * Vec3::Y).xy();
+            forward * throttle * ship_speed.0
+        };
+
+        linear_velocity.0 += acceleration * time.delta_secs();
+        linear_velocity.0 = linear_velocity.0.clamp_length_max(controller.max_speed);
     }
 }
 
-/// Applies movement to player. TODO: use movement controller here
-fn apply_player_movement(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<
+/// Turn the player ship according to the current [`ControlScheme`].
+fn apply_player_rotation(
+    time: Res<Time>,
+    control_scheme: Res<ControlScheme>,
+    input: ActionInput,
+    cursor_position: CursorPositionQuery,
+    player: Single<
         (
             &MovementController,
             &Transform,
-            &mut LinearVelocity,
-            &mut AngularVelocity,
-            &ShipSpeed,
             &RotationSpeed,
+            &mut AngularVelocity,
         ),
-        With<Player>,
+        (With<Player>, Without<Respawning>),
     >,
 ) {
-    for (
-        _controller,
-        transform,
-        mut linear_velocity,
-        _angular_velocity,
-        ship_speed,
-        _rotation_speed,
-    ) in query.iter_mut()
-    {
-        let _default_rotation_factor = 0.0;
-        let mut movement_factor = 0.0;
-
-        // let velocity = controller.max_speed * controller.intent;
-
-        // if keyboard_input.pressed(KeyCode::KeyA) {
-        //     default_rotation_factor += rotation_speed.0;
-        // }
-
-        // if keyboard_input.pressed(KeyCode::KeyD) {
-        //     default_rotation_factor -= rotation_speed.0;
-        // }
-
-        if keyboard_input.pressed(KeyCode::KeyW) {
-            movement_factor += 1.0;
+    let (controller, transform, rotation_speed, mut angular_velocity) = player.into_inner();
+    let aim_stick = input.right_stick();
+
+    match *control_scheme {
+        ControlScheme::Tank => {
+            // positive rotation around +Z is counter clockwise, so turning right is negative
+            if controller.intent.x != 0.0 {
+                angular_velocity.0 = -controller.intent.x.clamp(-1.0, 1.0) * rotation_speed.0;
+            }
+        }
+        // The right stick points where the ship should face, taking priority over the cursor
+        ControlScheme::CursorFollow | ControlScheme::TwinStick if aim_stick != Vec2::ZERO => {
+            let target = transform.translation.xy() + aim_stick.normalize() * STICK_AIM_DISTANCE;
+            if let Some(turn) = turn_towards(transform, target, rotation_speed.0, time.delta_secs())
+            {
+                angular_velocity.0 = turn;
+            }
+        }
+        // Only rotate towards cursor while aiming
+        ControlScheme::CursorFollow if !input.pressed(Action::Aim) => {}
+        ControlScheme::CursorFollow | ControlScheme::TwinStick => {
+            // Get the cursor translation in 2D
+            let Ok(cursor_translation) = cursor_position.get_world_position() else {
+                return; // cursor not in primary window
+            };
+
+            if let Some(turn) = turn_towards(
+                transform,
+                cursor_translation,
+                rotation_speed.0,
+                time.delta_secs(),
+            ) {
+                angular_velocity.0 = turn;
+            }
         }
-
-        // set rotation factor
-        // angular_velocity.0 = default_rotation_factor;
-
-        // get the ship's forward vector by applying the current rotation to the ships initial facing
-        // vector
-        let movement_direction = transform.rotation * Vec3::Y;
-        // get the distance the ship will move based on direction, the ship's movement speed and delta
-        // time
-        let movement_distance = movement_factor * ship_speed.0;
-        // create the change in translation using the new movement direction and distance
-        let translation_delta = movement_direction * movement_distance;
-
-        // update the ship translation with our new translation delta
-        linear_velocity.x = translation_delta.x;
-        linear_velocity.y = translation_delta.y;
     }
 }
 
-/// Rotate player towards cursor
-fn apply_player_rotation(
-    time: Res<Time>,
-    input: Res<ButtonInput<MouseButton>>,
-    cursor_position: CursorPositionQuery,
-    mut player_transform: Single<&mut Transform, With<Player>>,
-) {
-    // Only rotate towards cursor while holding button
-    if !input.pressed(MouseButton::Right) {
-        return;
+/// The angular velocity that turns `transform` to face `target` as fast as `max_speed` allows,
+/// without overshooting. Returns `None` if `target` is too close to aim at.
+pub fn turn_towards(
+    transform: &Transform,
+    target: Vec2,
+    max_speed: f32,
+    delta_secs: f32,
+) -> Option<f32> {
+    let to_target = target - transform.translation.xy();
+    if to_target.length() <= 50.0 || delta_secs <= 0.0 {
+        return None; // too close to target
     }
 
-    // Get the cursor translation in 2D
-    let Ok(cursor_translation) = cursor_position.get_world_position() else {
-        return; // cursor not in primary window
-    };
+    // Get the ship forward vector in 2D (already unit length)
+    let forward = (transform.rotation * Vec3::Y).xy();
 
-    // Check how close cursor is to player
-    let distance = cursor_translation.distance(player_transform.translation.xy());
-    if distance <= 50.0 {
-        return; // Player too close to cursor
-    }
+    // Signed angle from the forward vector to the target. Positive rotation around +Z is counter
+    // clockwise, following the right hand rule.
+    let angle = forward.angle_to(to_target);
 
-    // Get the player ship forward vector in 2D (already unit length)
-    let player_forward = (player_transform.rotation * Vec3::Y).xy();
-
-    // Get the vector from the player ship to the cursor in 2D and normalize it.
-    let to_cursor = (cursor_translation - player_transform.translation.xy()).normalize();
-
-    // Get the dot product between the player forward vector and the direction to the cursor.
-    let forward_dot_cursor = player_forward.dot(to_cursor);
-
-    // If the dot product is approximately 1.0 then the player is already facing the cursor and we can early out.
-    if (forward_dot_cursor - 1.0).abs() < f32::EPSILON {
-        return;
-    }
-
-    // Get the right vector of the player ship in 2D (already unit length)
-    let player_right = (player_transform.rotation * Vec3::X).xy();
-
-    // Get the dot product of the player right vector and the direction to the cursor.
-    // If the dot product is negative them we need to rotate counter clockwise, if it is
-    // positive we need to rotate clockwise. Note that `copysign` will still return 1.0 if the
-    // dot product is 0.0 (because the cursor is directly behind the player, so perpendicular
-    // with the right vector).
-    let right_dot_cursor = player_right.dot(to_cursor);
-
-    // Determine the sign of rotation from the right dot cursor. We need to negate the sign
-    // here as the 2D bevy co-ordinate system rotates around +Z, which is pointing out of the
-    // screen. Due to the right hand rule, positive rotation around +Z is counter clockwise and
-    // negative is clockwise.
-    let rotation_sign = -f32::copysign(1.0, right_dot_cursor);
-
-    // Limit rotation so we don't overshoot the target. We need to convert our dot product to
-    // an angle here so we can get an angle of rotation to clamp against.
-    let max_angle = ops::acos(forward_dot_cursor.clamp(-1.0, 1.0)); // Clamp acos for safety
-
-    // Calculate angle of rotation with limit
-    let rotation_speed = f32::to_radians(360.0);
-    let rotation_angle = rotation_sign * (rotation_speed * time.delta_secs()).min(max_angle);
-
-    // Rotate the player to face the cursor
-    player_transform.rotate_z(rotation_angle);
+    // Limit rotation so we don't overshoot the target this frame.
+    Some(angle.signum() * max_speed.min(angle.abs() / delta_secs))
 }
 
-/// Wrap objects when they go off screen
-fn apply_screen_wrap(
+/// Wrap or bounce objects that leave the world
+fn apply_world_edges(
+    edges: Res<WorldEdges>,
     window: Single<&Window, With<PrimaryWindow>>,
-    mut wrap_query: Query<&mut Transform, With<ScreenWrap>>,
+    mut bound_query: Query<(&mut Transform, Option<&mut LinearVelocity>), With<WorldBound>>,
 ) {
-    let size = window.size() + 256.0;
-    let half_size = size / 2.0;
-    for mut transform in &mut wrap_query {
-        let position = transform.translation.xy();
-        let wrapped = (position + half_size).rem_euclid(size) - half_size;
-        transform.translation = wrapped.extend(transform.translation.z);
+    match *edges {
+        WorldEdges::ScreenWrap => {
+            let size = window.size() + 256.0;
+            let half_size = size / 2.0;
+            for (mut transform, _) in &mut bound_query {
+                let position = transform.translation.xy();
+                let wrapped = (position + half_size).rem_euclid(size) - half_size;
+                transform.translation = wrapped.extend(transform.translation.z);
+            }
+        }
+        WorldEdges::Bounded(size) => {
+            let half_size = size / 2.0;
+            for (mut transform, velocity) in &mut bound_query {
+                let position = transform.translation.xy();
+                let clamped = position.clamp(-half_size, half_size);
+                if clamped == position {
+                    continue;
+                }
+                transform.translation = clamped.extend(transform.translation.z);
+
+                // send it back the way it came
+                let Some(mut velocity) = velocity else {
+                    continue;
+                };
+                if clamped.x != position.x {
+                    velocity.x = -position.x.signum() * velocity.x.abs();
+                }
+                if clamped.y != position.y {
+                    velocity.y = -position.y.signum() * velocity.y.abs();
+                }
+            }
+        }
+        WorldEdges::Infinite => {}
     }
 }
@@ -0,0 +1,314 @@
+//! Ore dropped by destroyed asteroids, and the cargo hold it gets collected into.
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        asteroid::{AsteroidDestroyed, AsteroidSize},
+        movement::WorldBound,
+        player::Player,
+        sector::Sector,
+        shapes::ShapeAssets,
+    },
+    screens::Screen,
+};
+use avian2d::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const ORE_DESPAWN_TIME_SECONDS: f32 = 12.0;
+/// Pickups blink for this long before they despawn.
+const ORE_EXPIRY_WARNING_SECONDS: f32 = 3.0;
+const ORE_PICKUP_RADIUS: f32 = 28.0;
+const ORE_SCATTER_SPEED: f32 = 40.0;
+/// Fraction of the destroyed asteroid's velocity passed on to its ore.
+const ORE_INHERITED_VELOCITY: f32 = 0.5;
+const ORE_SPRITE_SIZE: f32 = 8.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<OreKind>();
+    app.register_type::<OrePickup>();
+    app.register_type::<Cargo>();
+    app.add_message::<OreCollected>();
+
+    app.add_systems(
+        Update,
+        (
+            tick_ore_timers.in_set(AppSystems::TickTimers),
+            (
+                spawn_ore_drops.run_if(resource_exists::<ShapeAssets>),
+                collect_ore,
+                blink_expiring_ore,
+                despawn_expired_ore,
+            )
+                .chain()
+                .in_set(AppSystems::Update),
+        )
+            .in_set(PausableSystems),
+    );
+}
+
+/// The different types of ore that can be mined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum OreKind {
+    Iron,
+    Nickel,
+    Platinum,
+}
+
+impl OreKind {
+    pub const ALL: [Self; 3] = [Self::Iron, Self::Nickel, Self::Platinum];
+
+    /// Relative chance of this ore dropping from an asteroid.
+    fn drop_weight(self) -> f32 {
+        match self {
+            Self::Iron => 6.0,
+            Self::Nickel => 3.0,
+            Self::Platinum => 1.0,
+        }
+    }
+
+    /// Credits a station pays for one unit of this ore.
+    pub fn price(self) -> u32 {
+        match self {
+            Self::Iron => 5,
+            Self::Nickel => 12,
+            Self::Platinum => 30,
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Self::Iron => Color::srgb(0.72, 0.45, 0.32),
+            Self::Nickel => Color::srgb(0.62, 0.78, 0.62),
+            Self::Platinum => Color::srgb(0.85, 0.88, 0.98),
+        }
+    }
+}
+
+/// How many ore pickups an asteroid of the given size drops when destroyed, before the sector's
+/// ore richness is applied.
+fn ore_yield(size: AsteroidSize) -> usize {
+    match size {
+        AsteroidSize::Large => 3,
+        AsteroidSize::Medium => 2,
+        AsteroidSize::Small => 1,
+    }
+}
+
+/// A chunk of ore drifting through space, waiting to be collected.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+#[require(Visibility, RigidBody::Kinematic, WorldBound)]
+pub struct OrePickup {
+    pub kind: OreKind,
+    pub amount: u32,
+    pub despawn_timer: Timer,
+}
+
+/// What happens to ore that doesn't fit in a [`Cargo`] hold.
+/// Set per ship with `cargo_overflow` in its `.ship.ron` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Deserialize)]
+pub enum CargoOverflow {
+    /// Take as much as fits and leave the rest floating in space.
+    #[default]
+    LeaveInSpace,
+    /// Take as much as fits and destroy the rest.
+    Discard,
+}
+
+/// A ship's cargo hold.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Cargo {
+    /// Maximum total units of ore the hold can carry.
+    pub capacity: u32,
+    pub ore: HashMap<OreKind, u32>,
+    pub overflow: CargoOverflow,
+}
+
+impl Cargo {
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            capacity,
+            ore: HashMap::default(),
+            overflow: CargoOverflow::default(),
+        }
+    }
+
+    /// Total units of ore in the hold.
+    pub fn total(&self) -> u32 {
+        self.ore.values().sum()
+    }
+
+    pub fn free_space(&self) -> u32 {
+        self.capacity.saturating_sub(self.total())
+    }
+
+    /// Store as much of `amount` as fits, returning how much was stored.
+    pub fn add(&mut self, kind: OreKind, amount: u32) -> u32 {
+        let stored = amount.min(self.free_space());
+        if stored > 0 {
+            *self.ore.entry(kind).or_default() += stored;
+        }
+        stored
+    }
+}
+
+/// Sent when the player moves ore into their cargo hold.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct OreCollected {
+    /// Units of ore stored.
+    pub amount: u32,
+}
+
+/// An ore pickup drifting with the given velocity.
+pub fn ore_pickup(
+    kind: OreKind,
+    amount: u32,
+    position: Vec2,
+    velocity: Vec2,
+    shape_assets: &ShapeAssets,
+) -> impl Bundle {
+    (
+        Name::new(format!("{kind:?} Ore")),
+        OrePickup {
+            kind,
+            amount,
+            despawn_timer: Timer::from_seconds(ORE_DESPAWN_TIME_SECONDS, TimerMode::Once),
+        },
+        DespawnOnExit(Screen::Gameplay),
+        LinearVelocity(velocity),
+        Sprite {
+            image: shape_assets.circle.clone(),
+            color: kind.color(),
+            custom_size: Some(Vec2::splat(ORE_SPRITE_SIZE)),
+            ..default()
+        },
+        Transform::from_translation(position.extend(-1.0)),
+    )
+}
+
+/// progress timers for tracking ore despawning
+fn tick_ore_timers(mut query: Query<&mut OrePickup>, time: Res<Time>) {
+    for mut pickup in query.iter_mut() {
+        pickup.despawn_timer.tick(time.delta());
+    }
+}
+
+/// Scatter ore where asteroids were destroyed.
+fn spawn_ore_drops(
+    mut commands: Commands,
+    mut asteroid_destroyed: MessageReader<AsteroidDestroyed>,
+    shape_assets: Res<ShapeAssets>,
+    sector: Res<Sector>,
+) {
+    let rng = &mut rand::rng();
+
+    for destroyed in asteroid_destroyed.read() {
+        let count = (ore_yield(destroyed.size) as f32 * sector.ore_richness).round() as usize;
+        for _ in 0..count {
+            let Ok(kind) = OreKind::ALL.choose_weighted(rng, |kind| kind.drop_weight()) else {
+                continue;
+            };
+            let direction = Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU));
+            let velocity = destroyed.velocity * ORE_INHERITED_VELOCITY
+                + direction * rng.random_range(0.0..=ORE_SCATTER_SPEED);
+
+            commands.spawn(ore_pickup(
+                *kind,
+                1,
+                destroyed.position,
+                velocity,
+                &shape_assets,
+            ));
+        }
+    }
+}
+
+/// Move ore the player flies over into their cargo hold.
+fn collect_ore(
+    mut commands: Commands,
+    mut players: Query<(&Transform, &mut Cargo), With<Player>>,
+    mut pickups: Query<(Entity, &Transform, &mut OrePickup)>,
+    mut ore_collected: MessageWriter<OreCollected>,
+) {
+    for (player_transform, mut cargo) in &mut players {
+        let player_position = player_transform.translation.xy();
+
+        for (entity, transform, mut pickup) in &mut pickups {
+            if pickup.amount == 0
+                || transform.translation.xy().distance(player_position) > ORE_PICKUP_RADIUS
+            {
+                continue;
+            }
+
+            let stored = cargo.add(pickup.kind, pickup.amount);
+            pickup.amount -= stored;
+            if stored > 0 {
+                ore_collected.write(OreCollected { amount: stored });
+            }
+
+            if pickup.amount == 0 || cargo.overflow == CargoOverflow::Discard {
+                pickup.amount = 0;
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+/// Blink pickups that are about to despawn.
+fn blink_expiring_ore(mut query: Query<(&OrePickup, &mut Visibility)>) {
+    for (pickup, mut visibility) in &mut query {
+        let remaining = pickup.despawn_timer.remaining_secs();
+        *visibility = if remaining < ORE_EXPIRY_WARNING_SECONDS && (remaining * 8.0) as u32 % 2 == 0
+        {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+fn despawn_expired_ore(mut commands: Commands, query: Query<(Entity, &OrePickup)>) {
+    for (entity, pickup) in &query {
+        if pickup.despawn_timer.just_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_stores_everything_that_fits() {
+        let mut cargo = Cargo::new(10);
+        assert_eq!(cargo.add(OreKind::Iron, 4), 4);
+        assert_eq!(cargo.add(OreKind::Iron, 2), 2);
+        assert_eq!(cargo.add(OreKind::Nickel, 3), 3);
+        assert_eq!(cargo.ore[&OreKind::Iron], 6);
+        assert_eq!(cargo.ore[&OreKind::Nickel], 3);
+        assert_eq!(cargo.free_space(), 1);
+    }
+
+    #[test]
+    fn add_stops_at_capacity() {
+        let mut cargo = Cargo::new(5);
+        assert_eq!(cargo.add(OreKind::Platinum, 8), 5);
+        assert_eq!(cargo.total(), 5);
+        assert_eq!(cargo.add(OreKind::Iron, 1), 0);
+        // nothing stored means no empty entry either
+        assert!(!cargo.ore.contains_key(&OreKind::Iron));
+    }
+
+    #[test]
+    fn free_space_is_zero_when_over_capacity() {
+        let mut cargo = Cargo::new(10);
+        cargo.add(OreKind::Iron, 10);
+        cargo.capacity = 5;
+        assert_eq!(cargo.free_space(), 0);
+        assert_eq!(cargo.add(OreKind::Iron, 1), 0);
+    }
+}
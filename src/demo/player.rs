@@ -1,29 +1,49 @@
 //! Player-specific behavior.
 
-use super::weapon::{FireWeapon, Weapon};
+use super::weapon::{FireWeapon, Weapon, WeaponDefinition};
 use crate::{
     AppSystems, PausableSystems,
     asset_tracking::LoadResource,
+    camera::CameraTarget,
     demo::{
-        animation::{AnimationIndices, AnimationTimer, PlayerAnimation},
-        movement::{MovementController, RotationSpeed, ScreenWrap, ShipSpeed},
+        animation::{AnimationTimer, PlayerAnimation},
+        combat::{GameLayer, Health, Shield},
+        movement::{
+            ControlScheme, MovementController, ReverseThrusters, RotationSpeed, ShipSpeed,
+            WorldBound,
+        },
+        ore::Cargo,
+        respawn::Respawning,
+        score::Score,
+        ship::{ShipDefinition, ship_sprites},
+        upgrades::ShipModifiers,
     },
+    input::{Action, ActionInput},
 };
 use avian2d::prelude::*;
 use bevy::{
     image::{ImageLoaderSettings, ImageSampler},
+    input::mouse::AccumulatedMouseScroll,
     prelude::*,
 };
 
-const SHIP_SPEED: f32 = 320.0;
-const ROTATION_SPEED: f32 = 360.0;
-const POWERED_ANIMATION_INDICES: AnimationIndices = AnimationIndices { first: 0, last: 7 };
+const SHIP_LINEAR_DAMPING: f32 = 0.4;
+const SHIP_ANGULAR_DAMPING: f32 = 4.0;
+const SHIELD_RECHARGE_RATE: f32 = 1.0;
+const SHIELD_RECHARGE_DELAY: f32 = 3.0;
+const PLAYER_LIVES: u32 = 3;
+const ENGINE_ANIMATION_FPS: f32 = 12.0;
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
-#[require(Visibility, RigidBody::Kinematic, Sensor)]
+#[require(Visibility, RigidBody::Dynamic)]
 pub struct Player;
 
+/// Ships the player has left, including the current one.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Lives(pub u32);
+
 #[derive(Component)]
 pub struct PlayerShipEngineEffect;
 
@@ -59,32 +79,27 @@ impl FromWorld for PlayerAssets {
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct ShipAssets {
-    #[dependency]
-    pub fighter_base: Handle<Image>,
-    #[dependency]
-    pub fighter_engine_effect_sheet: Handle<Image>,
     #[dependency]
     pub projectile: Handle<Image>,
 }
 impl FromWorld for ShipAssets {
     fn from_world(world: &mut World) -> Self {
         let assets = world.resource::<AssetServer>();
-        let settings = |settings: &mut ImageLoaderSettings| {
-            // Use `nearest` image sampling to preserve pixel art style.
-            settings.sampler = ImageSampler::nearest();
-        };
-
         Self {
-            fighter_base: assets.load_with_settings("images/Fighter - Base.png", settings),
-            fighter_engine_effect_sheet: assets
-                .load_with_settings("images/Fighter - Engine.png", settings),
-            projectile: assets.load_with_settings("images/circle.png", settings),
+            projectile: assets.load_with_settings(
+                "images/circle.png",
+                |settings: &mut ImageLoaderSettings| {
+                    // Use `nearest` image sampling to preserve pixel art style.
+                    settings.sampler = ImageSampler::nearest();
+                },
+            ),
         }
     }
 }
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Player>();
+    app.register_type::<Lives>();
 
     app.register_type::<PlayerAssets>();
     app.load_resource::<PlayerAssets>();
@@ -95,7 +110,12 @@ pub(super) fn plugin(app: &mut App) {
     // Record directional input as movement controls.
     app.add_systems(
         Update,
-        (record_player_directional_input, player_weapon_controls)
+        (
+            record_player_directional_input,
+            switch_player_weapon,
+            player_weapon_controls,
+        )
+            .chain()
             .in_set(AppSystems::RecordInput)
             .in_set(PausableSystems),
     );
@@ -129,100 +149,125 @@ pub fn _player(
             max_speed,
             ..default()
         },
-        ScreenWrap,
+        WorldBound,
         player_animation,
     )
 }
 
-// TODO: change this for ship flight model
 fn record_player_directional_input(
-    input: Res<ButtonInput<KeyCode>>,
-    mut controller_query: Query<&mut MovementController, With<Player>>,
+    input: ActionInput,
+    control_scheme: Res<ControlScheme>,
+    mut controller_query: Query<&mut MovementController, (With<Player>, Without<Respawning>)>,
 ) {
     // Collect directional input.
     let mut intent = Vec2::ZERO;
-    if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
+    if input.pressed(Action::Thrust) {
         intent.y += 1.0;
     }
-    if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
+    if input.pressed(Action::Reverse) {
         intent.y -= 1.0;
     }
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+    if input.pressed(Action::RotateLeft) {
         intent.x -= 1.0;
     }
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+    if input.pressed(Action::RotateRight) {
         intent.x += 1.0;
     }
 
-    // Normalize intent so that diagonal movement is the same speed as horizontal / vertical.
-    // This should be omitted if the input comes from an analog stick instead.
-    let intent = intent.normalize_or_zero();
+    // Twin-stick input is a thrust direction, so normalize it so that diagonal thrust is the same
+    // as horizontal / vertical. Otherwise throttle (`y`) and turning (`x`) are independent axes.
+    // Analog stick input is added afterwards and never normalized, so partial deflection gives
+    // partial thrust.
+    let absolute = *control_scheme == ControlScheme::TwinStick;
+    let intent = if absolute {
+        intent.normalize_or_zero()
+    } else {
+        intent
+    };
+    let intent = (intent + input.left_stick()).clamp(Vec2::NEG_ONE, Vec2::ONE);
 
     // Apply movement intent to controllers.
     for mut controller in &mut controller_query {
         controller.intent = intent;
+        controller.absolute = absolute;
+    }
+}
+
+/// Cycle through the ship's weapons with the weapon actions or the mouse wheel.
+fn switch_player_weapon(
+    input: ActionInput,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    mut weapon: Single<&mut Weapon, (With<Player>, Without<Respawning>)>,
+) {
+    if input.just_pressed(Action::NextWeapon) || mouse_scroll.delta.y < 0.0 {
+        weapon.select_next();
+    } else if input.just_pressed(Action::PreviousWeapon) || mouse_scroll.delta.y > 0.0 {
+        weapon.select_previous();
     }
 }
 
 // trigger event to fire weapon
-// TODO: wire this up
 fn player_weapon_controls(
-    player: Single<Entity, With<Player>>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    player: Single<Entity, (With<Player>, Without<Respawning>)>,
+    control_scheme: Res<ControlScheme>,
+    input: ActionInput,
     mut fire_weapon: MessageWriter<FireWeapon>,
 ) {
-    // only fire weapon if holding right mouse and then clicking left mouse
-    // or hitting the spacebar
-    if mouse_input.pressed(MouseButton::Right)
-        && (mouse_input.pressed(MouseButton::Left) || keyboard_input.pressed(KeyCode::Space))
-    {
+    // when following the cursor, only fire weapon if aiming with the mouse or right stick
+    let aiming = *control_scheme != ControlScheme::CursorFollow
+        || input.pressed(Action::Aim)
+        || input.right_stick() != Vec2::ZERO;
+
+    if aiming && input.pressed(Action::Fire) {
         fire_weapon.write(FireWeapon { entity: *player });
     }
 }
 
-pub fn fighter_ship(
-    ship_assets: &Res<ShipAssets>,
-    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+/// The player's ship, built from its [`ShipDefinition`] with the player's upgrades applied.
+pub fn player_ship(
+    ship: &ShipDefinition,
+    modifiers: &ShipModifiers,
+    weapon_definitions: &Assets<WeaponDefinition>,
 ) -> impl Bundle {
-    // A texture atlas is a way to split a single image into a grid of related images.
-    // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(64), 8, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
     (
-        Name::new("Nairan Fighter"),
+        Name::new(ship.name.clone()),
         Player,
+        CameraTarget,
         MovementController {
-            max_speed: SHIP_SPEED,
+            max_speed: ship.max_speed,
             ..default()
         },
-        ScreenWrap,
-        Weapon::new(),
-        ShipSpeed(SHIP_SPEED),
-        RotationSpeed(f32::to_radians(ROTATION_SPEED)),
-        Collider::capsule(8.0, 12.0),
-        Transform::from_scale(Vec2::splat(1.6).extend(1.0)),
-        children![
-            (
-                Sprite::from_image(ship_assets.fighter_base.clone()),
-                Transform::from_xyz(0.0, 0.0, 2.0),
-            ),
+        WorldBound,
+        Weapon {
+            damage_multiplier: modifiers.damage,
+            fire_rate_multiplier: modifiers.fire_rate,
+            ..Weapon::new(&ship.hardpoints, weapon_definitions)
+        },
+        Cargo {
+            overflow: ship.cargo_overflow,
+            ..Cargo::new(ship.cargo_capacity + modifiers.cargo)
+        },
+        Health::new(ship.hull + modifiers.hull),
+        Shield::new(ship.shield, SHIELD_RECHARGE_RATE, SHIELD_RECHARGE_DELAY),
+        Score::default(),
+        Lives(PLAYER_LIVES),
+        ShipSpeed(ship.thrust * modifiers.thrust),
+        ReverseThrusters(ship.reverse_thrust),
+        RotationSpeed(ship.turn_rate * modifiers.turn_rate),
+        LinearDamping(SHIP_LINEAR_DAMPING),
+        AngularDamping(SHIP_ANGULAR_DAMPING),
+        ship.collider.collider(),
+        CollisionLayers::new(GameLayer::Player, LayerMask::ALL),
+        CollisionEventsEnabled,
+        Transform::from_scale(Vec2::splat(ship.scale).extend(1.0)),
+        ship_sprites(
+            ship,
             (
                 PlayerShipEngineEffect,
-                Sprite {
-                    image: ship_assets.fighter_engine_effect_sheet.clone(),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: texture_atlas_layout,
-                        index: 0,
-                    }),
-                    ..default()
-                },
-                Transform::from_xyz(0.0, -0.3, 0.0),
                 Visibility::Hidden, // will show effect later
-                POWERED_ANIMATION_INDICES,
-                AnimationTimer::with_fps(12.0),
+                ship.engine.indices,
+                AnimationTimer::with_fps(ENGINE_ANIMATION_FPS),
             ),
-        ],
+        ),
     )
 }
@@ -0,0 +1,334 @@
+//! A radar in the corner of the gameplay UI, showing what's around the player's ship.
+//!
+//! The radar tracks the same [`WorldBound`] entities the movement systems keep inside the world,
+//! plus anything marked as a [`PointOfInterest`]. Enemies and points of interest beyond the
+//! radar's range are shown as arrows on its edge.
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        asteroid::{ASTEROID_COLOR, Asteroid, AsteroidSize},
+        enemy::Enemy,
+        movement::WorldBound,
+        ore::OrePickup,
+        player::Player,
+    },
+    screens::Screen,
+    theme::palette::{BUTTON_HOVERED_BACKGROUND, PANEL_BACKGROUND},
+};
+
+const ASTEROID_BLIP_COLOR: Color = ASTEROID_COLOR;
+const ENEMY_BLIP_COLOR: Color = Color::srgb(0.918, 0.302, 0.255);
+const POINT_OF_INTEREST_BLIP_COLOR: Color = Color::srgb(0.867, 0.827, 0.412);
+const PLAYER_BLIP_COLOR: Color = Color::WHITE;
+const RADAR_BORDER_COLOR: Color = BUTTON_HOVERED_BACKGROUND;
+const RADAR_BORDER_WIDTH: f32 = 2.0;
+/// Pixel size of the arrows shown for targets out of range.
+const ARROW_SIZE: f32 = 8.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<RadarConfig>();
+    app.init_resource::<RadarConfig>();
+    app.register_type::<PointOfInterest>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_radar);
+    app.add_systems(
+        Update,
+        update_radar
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// How the radar looks and how far it sees.
+#[derive(Resource, Debug, Clone, Copy, Reflect)]
+#[reflect(Resource)]
+pub struct RadarConfig {
+    /// World units from the player to the edge of the radar.
+    pub range: f32,
+    /// Diameter of the radar, in pixels.
+    pub size: f32,
+}
+
+impl Default for RadarConfig {
+    fn default() -> Self {
+        Self {
+            range: 1500.0,
+            size: 180.0,
+        }
+    }
+}
+
+/// Something the player should be able to find, shown on the radar even when out of range.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct PointOfInterest;
+
+/// The radar's display, which blips are positioned within.
+#[derive(Component, Debug)]
+struct Radar;
+
+/// A dot on the radar. Blips are reused from frame to frame, and hidden when there are more blips
+/// than things to show.
+#[derive(Component, Debug)]
+struct RadarBlip;
+
+/// What kind of thing a blip stands for, which decides its icon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RadarIcon {
+    Asteroid(AsteroidSize),
+    Ore(Color),
+    Enemy,
+    PointOfInterest,
+}
+
+impl RadarIcon {
+    fn color(self) -> Color {
+        match self {
+            Self::Asteroid(_) => ASTEROID_BLIP_COLOR,
+            Self::Ore(color) => color,
+            Self::Enemy => ENEMY_BLIP_COLOR,
+            Self::PointOfInterest => POINT_OF_INTEREST_BLIP_COLOR,
+        }
+    }
+
+    /// Whether to point the way to this kind of target when it's out of range.
+    fn tracked_out_of_range(self) -> bool {
+        matches!(self, Self::Enemy | Self::PointOfInterest)
+    }
+}
+
+/// How a single blip should be drawn this frame.
+struct Blip {
+    /// Pixel position of the blip's center, from the top left of the radar.
+    center: Vec2,
+    size: f32,
+    background: Color,
+    border: UiRect,
+    border_color: Color,
+    border_radius: BorderRadius,
+    /// Clockwise rotation in radians.
+    rotation: f32,
+}
+
+impl Blip {
+    fn icon(icon: RadarIcon, center: Vec2) -> Self {
+        let color = icon.color();
+        let (size, border_radius, rotation) = match icon {
+            RadarIcon::Asteroid(size) => {
+                let diameter = match size {
+                    AsteroidSize::Large => 7.0,
+                    AsteroidSize::Medium => 5.0,
+                    AsteroidSize::Small => 3.0,
+                };
+                (diameter, BorderRadius::MAX, 0.0)
+            }
+            RadarIcon::Ore(_) => (3.0, BorderRadius::ZERO, 0.0),
+            // a diamond
+            RadarIcon::Enemy => (6.0, BorderRadius::ZERO, FRAC_PI_4),
+            RadarIcon::PointOfInterest => {
+                return Self {
+                    center,
+                    size: 9.0,
+                    background: Color::NONE,
+                    border: UiRect::all(Px(2.0)),
+                    border_color: color,
+                    border_radius: BorderRadius::MAX,
+                    rotation: 0.0,
+                };
+            }
+        };
+
+        Self {
+            center,
+            size,
+            background: color,
+            border: UiRect::ZERO,
+            border_color: Color::NONE,
+            border_radius,
+            rotation,
+        }
+    }
+
+    /// A chevron on the edge of the radar, pointing along `direction`.
+    fn arrow(icon: RadarIcon, center: Vec2, direction: Vec2) -> Self {
+        // the top and right borders of a square make a chevron pointing up and right, so turn it
+        // from there to face `direction`
+        let rotation = FRAC_PI_4 - direction.to_angle();
+        Self {
+            center,
+            size: ARROW_SIZE,
+            background: Color::NONE,
+            border: UiRect {
+                top: Px(2.0),
+                right: Px(2.0),
+                ..default()
+            },
+            border_color: icon.color(),
+            border_radius: BorderRadius::ZERO,
+            rotation,
+        }
+    }
+
+    fn node(&self) -> Node {
+        Node {
+            position_type: PositionType::Absolute,
+            left: Px(self.center.x - self.size / 2.0),
+            top: Px(self.center.y - self.size / 2.0),
+            width: Px(self.size),
+            height: Px(self.size),
+            border: self.border,
+            ..default()
+        }
+    }
+}
+
+fn spawn_radar(mut commands: Commands, config: Res<RadarConfig>) {
+    commands.spawn((
+        Name::new("Radar"),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Px(16.0),
+            bottom: Px(16.0),
+            width: Px(config.size),
+            height: Px(config.size),
+            border: UiRect::all(Px(RADAR_BORDER_WIDTH)),
+            ..default()
+        },
+        Radar,
+        BackgroundColor(PANEL_BACKGROUND.with_alpha(0.7)),
+        BorderColor::all(RADAR_BORDER_COLOR),
+        BorderRadius::MAX,
+        // Don't block picking events for the game or menus.
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        children![(
+            Name::new("Player Blip"),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Percent(50.0),
+                top: Percent(50.0),
+                width: Px(4.0),
+                height: Px(4.0),
+                margin: UiRect::all(Px(-2.0)),
+                ..default()
+            },
+            BackgroundColor(PLAYER_BLIP_COLOR),
+            // keep the player on top of the other blips
+            ZIndex(1),
+        )],
+    ));
+}
+
+/// Work out a blip for everything the radar can see, then reuse the existing blip nodes to draw
+/// them, spawning more if needed.
+fn update_radar(
+    mut commands: Commands,
+    config: Res<RadarConfig>,
+    player: Single<&Transform, With<Player>>,
+    radar: Single<Entity, With<Radar>>,
+    targets: Query<
+        (
+            &Transform,
+            Option<&Asteroid>,
+            Option<&OrePickup>,
+            Has<Enemy>,
+            Has<PointOfInterest>,
+        ),
+        (
+            Or<(With<WorldBound>, With<PointOfInterest>)>,
+            Without<Player>,
+        ),
+    >,
+    mut blips: Query<
+        (
+            &mut Node,
+            &mut BackgroundColor,
+            &mut BorderColor,
+            &mut BorderRadius,
+            &mut UiTransform,
+            &mut Visibility,
+        ),
+        With<RadarBlip>,
+    >,
+) {
+    // blips are positioned inside the radar's border
+    let radius = (config.size / 2.0 - RADAR_BORDER_WIDTH).max(1.0);
+    let player_position = player.translation.xy();
+
+    let visible = targets
+        .iter()
+        .filter_map(|(transform, asteroid, ore, enemy, point_of_interest)| {
+            let icon = if let Some(asteroid) = asteroid {
+                RadarIcon::Asteroid(asteroid.size)
+            } else if let Some(ore) = ore {
+                RadarIcon::Ore(ore.kind.color())
+            } else if enemy {
+                RadarIcon::Enemy
+            } else if point_of_interest {
+                RadarIcon::PointOfInterest
+            } else {
+                return None;
+            };
+
+            // flip y, as UI coordinates grow downwards
+            let offset = (transform.translation.xy() - player_position) / config.range;
+            let offset = Vec2::new(offset.x, -offset.y);
+            if offset.length() <= 1.0 {
+                Some(Blip::icon(icon, Vec2::splat(radius) + offset * radius))
+            } else if icon.tracked_out_of_range() {
+                let direction = offset.normalize();
+                let edge = radius - ARROW_SIZE;
+                Some(Blip::arrow(
+                    icon,
+                    Vec2::splat(radius) + direction * edge,
+                    Vec2::new(direction.x, -direction.y),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut visible = visible.into_iter();
+    for (
+        mut node,
+        mut background,
+        mut border_color,
+        mut border_radius,
+        mut transform,
+        mut visibility,
+    ) in &mut blips
+    {
+        let Some(blip) = visible.next() else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *node = blip.node();
+        *background = BackgroundColor(blip.background);
+        *border_color = BorderColor::all(blip.border_color);
+        *border_radius = blip.border_radius;
+        *transform = UiTransform::from_rotation(Rot2::radians(blip.rotation));
+        *visibility = Visibility::Inherited;
+    }
+
+    // draw the rest with new blips
+    for blip in visible {
+        commands.spawn((
+            Name::new("Radar Blip"),
+            RadarBlip,
+            blip.node(),
+            BackgroundColor(blip.background),
+            BorderColor::all(blip.border_color),
+            blip.border_radius,
+            UiTransform::from_rotation(Rot2::radians(blip.rotation)),
+            Visibility::Inherited,
+            ChildOf(*radar),
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+//! Destroying the player's ship, respawning it, and ending the run when the player runs out of
+//! lives.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        combat::{Health, Invulnerable, Shield},
+        explosion::explosion,
+        movement::MovementController,
+        player::{Lives, Player, ShipAssets},
+    },
+    screens::Screen,
+};
+
+const RESPAWN_DELAY_SECONDS: f32 = 2.5;
+const RESPAWN_INVULNERABILITY_SECONDS: f32 = 3.0;
+const SHIP_EXPLOSION_SIZE: f32 = 160.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            tick_respawn_timer.in_set(AppSystems::TickTimers),
+            (
+                destroy_player.run_if(resource_exists::<ShipAssets>),
+                respawn_player,
+            )
+                .chain()
+                .in_set(AppSystems::Update),
+        )
+            .in_set(PausableSystems),
+    );
+}
+
+/// The player's ship has been destroyed and is waiting to respawn. It ignores input, and is
+/// hidden with its physics disabled.
+#[derive(Component, Debug)]
+pub struct Respawning(Timer);
+
+fn tick_respawn_timer(time: Res<Time>, mut query: Query<&mut Respawning>) {
+    for mut respawning in &mut query {
+        respawning.0.tick(time.delta());
+    }
+}
+
+fn destroy_player(
+    mut commands: Commands,
+    ship_assets: Res<ShipAssets>,
+    mut player: Query<
+        (
+            Entity,
+            &Health,
+            &Transform,
+            &mut Lives,
+            &mut MovementController,
+        ),
+        (With<Player>, Changed<Health>, Without<Respawning>),
+    >,
+) {
+    for (entity, health, transform, mut lives, mut controller) in &mut player {
+        if !health.is_dead() {
+            continue;
+        }
+
+        commands.spawn(explosion(
+            transform.translation.xy(),
+            SHIP_EXPLOSION_SIZE,
+            ship_assets.projectile.clone(),
+        ));
+
+        lives.0 = lives.0.saturating_sub(1);
+        controller.intent = Vec2::ZERO;
+        commands.entity(entity).insert((
+            Respawning(Timer::from_seconds(RESPAWN_DELAY_SECONDS, TimerMode::Once)),
+            Visibility::Hidden,
+            RigidBodyDisabled,
+            ColliderDisabled,
+            LinearVelocity::ZERO,
+            AngularVelocity::ZERO,
+        ));
+    }
+}
+
+/// Bring the ship back at the level origin once its respawn delay is over, or end the run if
+/// that was the last life.
+fn respawn_player(
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut player: Query<
+        (
+            Entity,
+            &Respawning,
+            &Lives,
+            &mut Health,
+            Option<&mut Shield>,
+            &mut Transform,
+        ),
+        With<Player>,
+    >,
+) {
+    for (entity, respawning, lives, mut health, shield, mut transform) in &mut player {
+        if !respawning.0.is_finished() {
+            continue;
+        }
+
+        if lives.0 == 0 {
+            next_screen.set(Screen::GameOver);
+            continue;
+        }
+
+        health.current = health.max;
+        if let Some(mut shield) = shield {
+            shield.current = shield.max;
+        }
+        transform.translation = Vec3::new(0.0, 0.0, transform.translation.z);
+        transform.rotation = Quat::IDENTITY;
+
+        commands
+            .entity(entity)
+            .remove::<(Respawning, RigidBodyDisabled, ColliderDisabled)>()
+            .insert((
+                Visibility::Inherited,
+                Invulnerable::from_seconds(RESPAWN_INVULNERABILITY_SECONDS),
+            ));
+    }
+}
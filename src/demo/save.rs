@@ -0,0 +1,379 @@
+//! Save slots, which keep the player's progress between sessions.
+//!
+//! Each slot holds the player's credits and upgrades, and the run they were on when they last
+//! saved from the pause menu. Credits and upgrades are also saved to the active slot whenever they
+//! change, so nothing bought is lost by quitting without saving. Changes during a run save the run
+//! too, so the saved run never holds ore that has already been sold.
+//!
+//! Slots are saved as `save1`, `save2`, ... through the [`storage`](crate::storage) module. Saves
+//! from older versions of the game are migrated when they are loaded.
+
+use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    demo::{
+        ore::{Cargo, OreKind},
+        player::Player,
+        score::Score,
+        sector::SectorSeed,
+        ship::SelectedShip,
+        station::Credits,
+        stats::RunStats,
+        upgrades::{Upgrade, Upgrades},
+    },
+    screens::Screen,
+    storage::{self, Versioned},
+};
+
+pub const SLOT_COUNT: usize = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SaveSlots>();
+
+    let slots = SaveSlots::load();
+    if let Some(save) = slots.active_save() {
+        app.insert_resource(Credits(save.credits));
+        app.insert_resource(Upgrades::new(save.upgrades.clone()));
+    }
+    app.insert_resource(slots);
+
+    app.add_systems(
+        Update,
+        (
+            save_progress.run_if(resource_changed::<Credits>.or(resource_changed::<Upgrades>)),
+            resume_run.run_if(in_state(Screen::Gameplay).and(resource_exists::<ResumedRun>)),
+        ),
+    );
+    app.add_systems(OnEnter(Screen::GameOver), end_saved_run);
+}
+
+/// Everything kept in a save slot.
+///
+/// Missing fields fall back to their defaults, so saves from an older version of the game still
+/// load.
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveData {
+    /// The format version of the save, see [`Versioned`].
+    version: u32,
+    /// Goes up with every save across all slots, so the most recent save can be found.
+    sequence: u64,
+    pub credits: u32,
+    pub upgrades: Vec<Upgrade>,
+    /// The run the player was on when they saved, if it hasn't ended since.
+    pub run: Option<SavedRun>,
+}
+
+/// A run in progress, as it was when saved.
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedRun {
+    pub sector_seed: u64,
+    /// The `.ship.ron` file of the ship being flown, see [`SelectedShip::path`].
+    pub ship: String,
+    pub cargo: HashMap<OreKind, u32>,
+    pub stats: RunStats,
+}
+
+impl SaveData {
+    pub fn new(credits: &Credits, upgrades: &Upgrades, run: Option<SavedRun>) -> Self {
+        Self {
+            version: Self::VERSION,
+            sequence: 0,
+            credits: credits.0,
+            upgrades: upgrades.purchased().to_vec(),
+            run,
+        }
+    }
+}
+
+impl Versioned for SaveData {
+    const VERSION: u32 = 3;
+
+    fn parse(version: u32, contents: &str) -> Result<Self, String> {
+        if version < 3 {
+            // runs used to store their ship as an index, and could hold ore that had already been
+            // sold, so only the progress is kept
+            let old: SaveDataV2 = storage::from_ron(contents)?;
+            return Ok(Self {
+                version: Self::VERSION,
+                sequence: old.sequence,
+                credits: old.credits,
+                upgrades: old.upgrades,
+                run: None,
+            });
+        }
+
+        let save: Self = storage::from_ron(contents)?;
+        Ok(Self {
+            version: Self::VERSION,
+            ..save
+        })
+    }
+}
+
+/// The parts of a version 1 or 2 save that are still used. Version 1 had no `sequence`.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct SaveDataV2 {
+    sequence: u64,
+    credits: u32,
+    upgrades: Vec<Upgrade>,
+}
+
+impl SavedRun {
+    pub fn new(
+        sector_seed: &SectorSeed,
+        selected_ship: &SelectedShip,
+        cargo: &Cargo,
+        stats: &RunStats,
+    ) -> Self {
+        Self {
+            sector_seed: sector_seed.seed,
+            ship: selected_ship.path().unwrap_or_default().to_string(),
+            cargo: cargo.ore.clone(),
+            stats: stats.clone(),
+        }
+    }
+}
+
+/// The save slots, as last loaded or saved.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct SaveSlots {
+    slots: [Option<SaveData>; SLOT_COUNT],
+    /// The slot progress is saved to. Starts as the most recently saved slot.
+    pub active: usize,
+}
+
+impl SaveSlots {
+    fn file(slot: usize) -> String {
+        format!("save{}", slot + 1)
+    }
+
+    /// Load every slot.
+    fn load() -> Self {
+        let mut slots = Self {
+            slots: std::array::from_fn(|slot| storage::load(&Self::file(slot))),
+            active: 0,
+        };
+        slots.active = (0..SLOT_COUNT)
+            .filter_map(|slot| Some((slot, slots.get(slot)?.sequence)))
+            .max_by_key(|(_, sequence)| *sequence)
+            .map_or(0, |(slot, _)| slot);
+        slots
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&SaveData> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    pub fn active_save(&self) -> Option<&SaveData> {
+        self.get(self.active)
+    }
+
+    /// Save to `slot`, which becomes the active slot.
+    pub fn save(&mut self, slot: usize, mut save: SaveData) {
+        if slot >= SLOT_COUNT {
+            return error!("there is no save slot {slot}.");
+        }
+
+        save.version = SaveData::VERSION;
+        save.sequence = self
+            .slots
+            .iter()
+            .flatten()
+            .map(|save| save.sequence)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        storage::save(&Self::file(slot), &save);
+        self.slots[slot] = Some(save);
+        self.active = slot;
+    }
+}
+
+/// A saved run, waiting to be restored once gameplay starts.
+#[derive(Resource, Debug)]
+pub struct ResumedRun(SavedRun);
+
+/// Loads save slots into the game.
+#[derive(SystemParam)]
+pub struct SaveLoader<'w, 's> {
+    commands: Commands<'w, 's>,
+    slots: ResMut<'w, SaveSlots>,
+    credits: ResMut<'w, Credits>,
+    upgrades: ResMut<'w, Upgrades>,
+    sector_seed: ResMut<'w, SectorSeed>,
+    selected_ship: ResMut<'w, SelectedShip>,
+}
+
+impl SaveLoader<'_, '_> {
+    /// Load the active slot, see [`SaveLoader::load`].
+    pub fn load_active(&mut self) -> bool {
+        let slot = self.slots.active;
+        self.load(slot)
+    }
+
+    /// Make `slot` the active slot and load its progress, starting from nothing if the slot is
+    /// empty. Returns whether the slot has a run to resume, in which case it will be resumed the
+    /// next time gameplay starts.
+    pub fn load(&mut self, slot: usize) -> bool {
+        if slot >= SLOT_COUNT {
+            error!("there is no save slot {slot}.");
+            return false;
+        }
+
+        self.slots.active = slot;
+        let save = self.slots.get(slot).cloned().unwrap_or_default();
+        self.credits.0 = save.credits;
+        *self.upgrades = Upgrades::new(save.upgrades);
+
+        let Some(run) = save.run else {
+            self.commands.remove_resource::<ResumedRun>();
+            return false;
+        };
+        let Some(ship) = SelectedShip::from_path(&run.ship) else {
+            warn!(
+                "Can't resume the saved run, its ship `{}` doesn't exist",
+                run.ship
+            );
+            self.commands.remove_resource::<ResumedRun>();
+            return false;
+        };
+        self.sector_seed.seed = run.sector_seed;
+        *self.selected_ship = ship;
+        self.commands.insert_resource(ResumedRun(run));
+        true
+    }
+}
+
+/// Whether a saved run is waiting to be resumed.
+pub fn resuming_run(resumed_run: Option<Res<ResumedRun>>) -> bool {
+    resumed_run.is_some()
+}
+
+/// Keep the active slot's credits and upgrades up to date.
+///
+/// During a run, credits change by trading at a station, so the run is saved along with them.
+/// Keeping the slot's old run would let the player sell their ore, quit, and continue with it back
+/// in the hold.
+fn save_progress(
+    credits: Res<Credits>,
+    upgrades: Res<Upgrades>,
+    mut slots: ResMut<SaveSlots>,
+    sector_seed: Res<SectorSeed>,
+    selected_ship: Res<SelectedShip>,
+    stats: Res<RunStats>,
+    player: Query<&Cargo, With<Player>>,
+) {
+    // the progress was just loaded, so there's nothing new to save
+    if credits.is_added() && upgrades.is_added() {
+        return;
+    }
+
+    let slot = slots.active;
+    let run = match player.single() {
+        Ok(cargo) => Some(SavedRun::new(&sector_seed, &selected_ship, cargo, &stats)),
+        // not on a run, so the slot's run is still as it was saved
+        Err(_) => slots.get(slot).and_then(|save| save.run.clone()),
+    };
+    slots.save(slot, SaveData::new(&credits, &upgrades, run));
+}
+
+/// Put the player's cargo and the run's stats back the way they were saved.
+fn resume_run(
+    mut commands: Commands,
+    resumed_run: Res<ResumedRun>,
+    player: Single<(&mut Cargo, &mut Score), With<Player>>,
+    mut stats: ResMut<RunStats>,
+) {
+    let (mut cargo, mut score) = player.into_inner();
+    let run = &resumed_run.0;
+    cargo.ore = run.cargo.clone();
+    score.0 = run.stats.score;
+    *stats = run.stats.clone();
+    commands.remove_resource::<ResumedRun>();
+}
+
+/// A run that has ended can't be continued, so drop it from the active slot.
+fn end_saved_run(mut slots: ResMut<SaveSlots>) {
+    let slot = slots.active;
+    let Some(save) = slots.get(slot).filter(|save| save.run.is_some()) else {
+        return;
+    };
+    let save = SaveData {
+        run: None,
+        ..save.clone()
+    };
+    slots.save(slot, save);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_migrates_version_1_progress() {
+        let save: SaveData =
+            storage::parse("(version: 1, credits: 120, upgrades: [Hold1, Thrusters1])").unwrap();
+        assert_eq!(save.version, SaveData::VERSION);
+        assert_eq!(save.sequence, 0);
+        assert_eq!(save.credits, 120);
+        assert_eq!(save.upgrades, [Upgrade::Hold1, Upgrade::Thrusters1]);
+        assert!(save.run.is_none());
+    }
+
+    #[test]
+    fn parse_migrates_version_2_without_its_run() {
+        let save: SaveData = storage::parse(
+            "(
+                version: 2,
+                sequence: 7,
+                credits: 40,
+                upgrades: [Plating1],
+                run: Some((sector_seed: 9, ship: 1, cargo: { Iron: 3 })),
+            )",
+        )
+        .unwrap();
+        assert_eq!(save.version, SaveData::VERSION);
+        assert_eq!(save.sequence, 7);
+        assert_eq!(save.credits, 40);
+        assert_eq!(save.upgrades, [Upgrade::Plating1]);
+        assert!(save.run.is_none());
+    }
+
+    #[test]
+    fn parse_keeps_current_saves() {
+        let run = SavedRun {
+            sector_seed: 9,
+            ship: "ships/hauler.ship.ron".to_string(),
+            cargo: HashMap::from_iter([(OreKind::Nickel, 4)]),
+            stats: RunStats::default(),
+        };
+        let save = SaveData::new(
+            &Credits(75),
+            &Upgrades::new(vec![Upgrade::Gyros1]),
+            Some(run),
+        );
+        let contents = ron::ser::to_string_pretty(&save, default()).unwrap();
+
+        let parsed: SaveData = storage::parse(&contents).unwrap();
+        assert_eq!(parsed.credits, 75);
+        assert_eq!(parsed.upgrades, [Upgrade::Gyros1]);
+        let run = parsed.run.unwrap();
+        assert_eq!(run.sector_seed, 9);
+        assert_eq!(
+            SelectedShip::from_path(&run.ship).map(|ship| ship.0),
+            Some(2)
+        );
+        assert_eq!(run.cargo[&OreKind::Nickel], 4);
+    }
+
+    #[test]
+    fn parse_rejects_newer_versions() {
+        let contents = format!("(version: {}, credits: 10)", SaveData::VERSION + 1);
+        assert!(storage::parse::<SaveData>(&contents).is_err());
+    }
+}
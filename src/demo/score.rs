@@ -0,0 +1,46 @@
+//! Points the player earns during a run.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{asteroid::AsteroidDestroyed, enemy::EnemyDestroyed, player::Player},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Score>();
+
+    app.add_systems(
+        Update,
+        (award_asteroid_score, award_enemy_score)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// The player's score for the current run.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Score(pub u32);
+
+fn award_asteroid_score(
+    mut destroyed: MessageReader<AsteroidDestroyed>,
+    score: Single<(Entity, &mut Score), With<Player>>,
+) {
+    let (player, mut score) = score.into_inner();
+    // asteroids caught in enemy fire are worth nothing
+    for asteroid in destroyed.read() {
+        if asteroid.destroyed_by == Some(player) {
+            score.0 += asteroid.size.score();
+        }
+    }
+}
+
+fn award_enemy_score(
+    mut destroyed: MessageReader<EnemyDestroyed>,
+    mut score: Single<&mut Score, With<Player>>,
+) {
+    for enemy in destroyed.read() {
+        score.0 += enemy.score;
+    }
+}
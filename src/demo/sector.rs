@@ -0,0 +1,382 @@
+//! Procedurally generated sectors. Everything about a sector's layout, from its asteroid belts to
+//! where enemies warp in, comes from a single `u64` seed, so the same seed always builds the same
+//! sector. Share the seed to replay a sector, or start the game with `--seed <seed>`.
+
+use bevy::prelude::*;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        asteroid::{AsteroidField, AsteroidSize},
+        combat::{DamageDealt, Health, Invulnerable, Shield, deal_damage},
+        enemy::Enemy,
+        player::Player,
+        radar::PointOfInterest,
+        respawn::Respawning,
+        shapes::ShapeAssets,
+    },
+    screens::Screen,
+};
+
+const HAZARD_COLOR: Color = Color::srgba(0.45, 0.85, 0.35, 0.18);
+const HAZARD_DAMAGE_INTERVAL_SECONDS: f32 = 1.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SectorSeed>();
+    app.insert_resource(SectorSeed::from_args().unwrap_or_default());
+
+    app.register_type::<Sector>();
+    app.init_resource::<Sector>();
+
+    app.register_type::<Hazard>();
+    app.add_systems(OnEnter(Screen::ShipSelect), roll_sector_seed);
+    app.add_systems(
+        Update,
+        (
+            tick_hazards.in_set(AppSystems::TickTimers),
+            damage_ships_in_hazards.in_set(AppSystems::Update),
+        )
+            .in_set(PausableSystems),
+    );
+}
+
+/// The seed the next sector is generated from.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct SectorSeed {
+    pub seed: u64,
+    /// Keep flying the same sector instead of rolling a new seed for each run.
+    pub fixed: bool,
+}
+
+impl Default for SectorSeed {
+    fn default() -> Self {
+        Self {
+            seed: rand::rng().random(),
+            fixed: false,
+        }
+    }
+}
+
+impl SectorSeed {
+    /// A fixed seed passed on the command line with `--seed <seed>`.
+    fn from_args() -> Option<Self> {
+        let mut args = std::env::args();
+        args.find(|arg| arg == "--seed")?;
+        let seed = args.next()?.parse().ok()?;
+        Some(Self { seed, fixed: true })
+    }
+}
+
+/// Tuning for how sectors are generated.
+#[derive(Debug, Clone, Reflect)]
+pub struct SectorConfig {
+    /// The area the sector is laid out in, centered on the level origin.
+    pub extent: Vec2,
+    pub min_belts: usize,
+    pub max_belts: usize,
+    pub min_asteroids_per_belt: usize,
+    pub max_asteroids_per_belt: usize,
+    /// How far asteroids can stray from the center line of their belt.
+    pub belt_width: f32,
+    /// Multiplier on the ore dropped by destroyed asteroids.
+    pub min_ore_richness: f32,
+    pub max_ore_richness: f32,
+    pub max_hazards: usize,
+    pub min_hazard_radius: f32,
+    pub max_hazard_radius: f32,
+    /// Damage dealt every second to ships inside a hazard.
+    pub hazard_damage: f32,
+    /// How many points around the edge of the sector enemies warp in from.
+    pub enemy_spawn_points: usize,
+    /// How far the station can be from the level origin.
+    pub min_station_distance: f32,
+    pub max_station_distance: f32,
+}
+
+impl Default for SectorConfig {
+    fn default() -> Self {
+        Self {
+            extent: Vec2::new(1280.0, 720.0),
+            min_belts: 1,
+            max_belts: 3,
+            min_asteroids_per_belt: 2,
+            max_asteroids_per_belt: 5,
+            belt_width: 60.0,
+            min_ore_richness: 0.5,
+            max_ore_richness: 2.0,
+            max_hazards: 2,
+            min_hazard_radius: 60.0,
+            max_hazard_radius: 140.0,
+            hazard_damage: 1.0,
+            enemy_spawn_points: 3,
+            min_station_distance: 300.0,
+            max_station_distance: 500.0,
+        }
+    }
+}
+
+/// The layout of the sector being played, generated from [`Sector::seed`].
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct Sector {
+    pub seed: u64,
+    /// Asteroids placed along the sector's belts when the level starts.
+    pub asteroids: Vec<AsteroidSpawn>,
+    /// Multiplier on the ore dropped by destroyed asteroids.
+    pub ore_richness: f32,
+    pub hazards: Vec<HazardZone>,
+    /// Where enemies warp in, taken in turn by each wave.
+    pub enemy_spawns: Vec<Vec2>,
+    /// Where the sector's station is.
+    pub station: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct AsteroidSpawn {
+    pub size: AsteroidSize,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+/// A cloud of radiation that slowly damages any ship inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct HazardZone {
+    pub position: Vec2,
+    pub radius: f32,
+    pub damage: f32,
+}
+
+impl Default for Sector {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            asteroids: Vec::new(),
+            ore_richness: 1.0,
+            hazards: Vec::new(),
+            enemy_spawns: Vec::new(),
+            station: Vec2::ZERO,
+        }
+    }
+}
+
+impl Sector {
+    /// Lay out a sector. The same `seed` and config always give the same sector.
+    pub fn generate(seed: u64, config: &SectorConfig, field: &AsteroidField) -> Self {
+        let rng = &mut ChaCha8Rng::seed_from_u64(seed);
+        let half_extent = config.extent / 2.0;
+
+        // belts are arcs of a circle, sweeping through the sector from somewhere off to the side
+        let mut asteroids = Vec::new();
+        for _ in 0..rng.random_range(config.min_belts..=config.max_belts) {
+            let center = random_point(rng, half_extent);
+            let radius = rng.random_range(half_extent.min_element()..=half_extent.length());
+            let start_angle = rng.random_range(0.0..std::f32::consts::TAU);
+            let sweep = rng.random_range(0.6..=1.6);
+            let count =
+                rng.random_range(config.min_asteroids_per_belt..=config.max_asteroids_per_belt);
+            // all asteroids in a belt drift the same way around it
+            let drift = if rng.random() { 1.0 } else { -1.0 };
+
+            for i in 0..count {
+                let along = start_angle + sweep * (i as f32 + rng.random::<f32>()) / count as f32;
+                let offset = rng.random_range(-config.belt_width..=config.belt_width);
+                let direction = Vec2::from_angle(along);
+                let mut position = center + direction * (radius + offset);
+                position = position.clamp(-half_extent, half_extent);
+                if position.length() < field.clear_radius {
+                    position = position.normalize_or(Vec2::X) * field.clear_radius;
+                }
+
+                let Ok(size) =
+                    AsteroidSize::ALL.choose_weighted(rng, |size| field.size_config(*size).weight)
+                else {
+                    continue;
+                };
+                let speed = rng.random_range(field.min_speed..=field.max_speed);
+
+                asteroids.push(AsteroidSpawn {
+                    size: *size,
+                    position,
+                    velocity: direction.perp() * drift * speed,
+                });
+            }
+        }
+
+        let ore_richness = rng.random_range(config.min_ore_richness..=config.max_ore_richness);
+
+        let hazards = (0..rng.random_range(0..=config.max_hazards))
+            .map(|_| {
+                let radius = rng.random_range(config.min_hazard_radius..=config.max_hazard_radius);
+                // keep the player's spawn point out of the cloud
+                let mut position = random_point(rng, half_extent);
+                let clearance = field.clear_radius + radius;
+                if position.length() < clearance {
+                    position = position.normalize_or(Vec2::Y) * clearance;
+                }
+                HazardZone {
+                    position,
+                    radius,
+                    damage: config.hazard_damage,
+                }
+            })
+            .collect();
+
+        let first_spawn_angle = rng.random_range(0.0..std::f32::consts::TAU);
+        let enemy_spawns = (0..config.enemy_spawn_points.max(1))
+            .map(|i| {
+                let angle = first_spawn_angle
+                    + std::f32::consts::TAU * i as f32 / config.enemy_spawn_points.max(1) as f32;
+                Vec2::from_angle(angle) * half_extent.min_element()
+            })
+            .collect();
+
+        // close enough to the start to find, but not right on top of it
+        let station = Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU))
+            * rng.random_range(config.min_station_distance..=config.max_station_distance);
+
+        Self {
+            seed,
+            asteroids,
+            ore_richness,
+            hazards,
+            enemy_spawns,
+            station,
+        }
+    }
+
+    /// An RNG for the given wave, so the same sector always sends the same waves.
+    pub fn wave_rng(&self, wave: u32) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(wave as u64);
+        rng
+    }
+
+    /// Where the `index`th enemy of a wave warps in.
+    pub fn enemy_spawn(&self, wave: u32, index: usize) -> Vec2 {
+        if self.enemy_spawns.is_empty() {
+            return Vec2::ZERO;
+        }
+        self.enemy_spawns[(wave as usize + index) % self.enemy_spawns.len()]
+    }
+}
+
+fn random_point(rng: &mut impl Rng, half_extent: Vec2) -> Vec2 {
+    Vec2::new(
+        rng.random_range(-half_extent.x..=half_extent.x),
+        rng.random_range(-half_extent.y..=half_extent.y),
+    )
+}
+
+/// A hazard in the level, damaging ships within its radius once per damage interval.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+#[require(Visibility)]
+pub struct Hazard {
+    pub radius: f32,
+    pub damage: f32,
+    pub damage_timer: Timer,
+}
+
+pub fn hazard(zone: HazardZone, shape_assets: &ShapeAssets) -> impl Bundle {
+    (
+        Name::new("Hazard"),
+        PointOfInterest,
+        Hazard {
+            radius: zone.radius,
+            damage: zone.damage,
+            damage_timer: Timer::from_seconds(HAZARD_DAMAGE_INTERVAL_SECONDS, TimerMode::Repeating),
+        },
+        DespawnOnExit(Screen::Gameplay),
+        Sprite {
+            image: shape_assets.circle.clone(),
+            color: HAZARD_COLOR,
+            custom_size: Some(Vec2::splat(zone.radius * 2.0)),
+            ..default()
+        },
+        Transform::from_translation(zone.position.extend(-2.0)),
+    )
+}
+
+/// Roll a new sector for the next run, unless the player is replaying a fixed seed.
+fn roll_sector_seed(mut sector_seed: ResMut<SectorSeed>) {
+    if !sector_seed.fixed {
+        sector_seed.seed = rand::rng().random();
+    }
+}
+
+fn tick_hazards(time: Res<Time>, mut query: Query<&mut Hazard>) {
+    for mut hazard in &mut query {
+        hazard.damage_timer.tick(time.delta());
+    }
+}
+
+fn damage_ships_in_hazards(
+    hazards: Query<(Entity, &Hazard, &Transform)>,
+    mut ships: Query<
+        (Entity, &Transform, &mut Health, Option<&mut Shield>),
+        (
+            Or<(With<Player>, With<Enemy>)>,
+            Without<Invulnerable>,
+            Without<Respawning>,
+        ),
+    >,
+    mut damage_dealt: MessageWriter<DamageDealt>,
+) {
+    for (source, hazard, hazard_transform) in &hazards {
+        if !hazard.damage_timer.just_finished() {
+            continue;
+        }
+        let center = hazard_transform.translation.xy();
+
+        for (target, transform, mut health, shield) in &mut ships {
+            if health.is_dead() || transform.translation.xy().distance(center) > hazard.radius {
+                continue;
+            }
+
+            deal_damage(&mut health, shield, hazard.damage);
+            damage_dealt.write(DamageDealt {
+                target,
+                source,
+                projectile: None,
+                amount: hazard.damage,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(seed: u64) -> Sector {
+        Sector::generate(seed, &SectorConfig::default(), &AsteroidField::default())
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_sector() {
+        assert_eq!(generate(42), generate(42));
+        assert_eq!(generate(u64::MAX), generate(u64::MAX));
+    }
+
+    #[test]
+    fn different_seeds_give_different_sectors() {
+        assert_ne!(generate(1), generate(2));
+        assert_ne!(generate(0), generate(u64::MAX));
+    }
+
+    #[test]
+    fn generated_sector_respects_the_config() {
+        let config = SectorConfig::default();
+        for seed in 0..32 {
+            let sector = Sector::generate(seed, &config, &AsteroidField::default());
+            assert!(sector.hazards.len() <= config.max_hazards);
+            assert_eq!(sector.enemy_spawns.len(), config.enemy_spawn_points);
+            let station_distance = sector.station.length();
+            assert!(station_distance >= config.min_station_distance - 0.01);
+            assert!(station_distance <= config.max_station_distance + 0.01);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Plain shapes shared by everything drawn as a tinted sprite rather than with its own art.
+
+use bevy::{
+    image::{ImageLoaderSettings, ImageSampler},
+    prelude::*,
+};
+
+use crate::asset_tracking::LoadResource;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ShapeAssets>();
+    app.load_resource::<ShapeAssets>();
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ShapeAssets {
+    /// A white circle, sized with [`Sprite::custom_size`] and tinted with [`Sprite::color`].
+    #[dependency]
+    pub circle: Handle<Image>,
+}
+
+impl FromWorld for ShapeAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            circle: assets.load_with_settings(
+                "images/circle.png",
+                |settings: &mut ImageLoaderSettings| {
+                    // Use `nearest` image sampling to preserve pixel art style.
+                    settings.sampler = ImageSampler::nearest();
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! Playable ship hulls, loaded from `.ship.ron` files under `assets/ships`.
+
+use avian2d::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::spawn::SpawnWith,
+    image::{ImageLoaderSettings, ImageSampler},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    asset_tracking::LoadResource,
+    demo::{animation::AnimationIndices, ore::CargoOverflow, weapon::Hardpoint},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<ShipDefinition>();
+    app.init_asset_loader::<ShipDefinitionLoader>();
+
+    app.register_type::<HullAssets>();
+    app.load_resource::<HullAssets>();
+
+    app.register_type::<SelectedShip>();
+    app.init_resource::<SelectedShip>();
+}
+
+/// Everything that sets one hull apart from another.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct ShipDefinition {
+    pub name: String,
+    pub description: String,
+    pub hull_sprite: Handle<Image>,
+    pub tint: Color,
+    /// Scale applied to the sprites and collider.
+    pub scale: f32,
+    /// Shown while thrusting.
+    pub engine: SpriteSheet,
+    /// Drawn under the hull, showing the first frame of the sheet.
+    pub weapon_mounts: Option<SpriteSheet>,
+    pub collider: ColliderShape,
+    pub max_speed: f32,
+    pub thrust: f32,
+    /// Fraction of the thrust available in reverse.
+    pub reverse_thrust: f32,
+    /// Radians per second.
+    pub turn_rate: f32,
+    pub hull: f32,
+    pub shield: f32,
+    pub cargo_capacity: u32,
+    pub cargo_overflow: CargoOverflow,
+    /// The weapons fitted to the ship, in the order they are cycled through.
+    pub hardpoints: Vec<Hardpoint>,
+}
+
+/// A horizontal strip of animation frames.
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    pub indices: AnimationIndices,
+}
+
+/// The shape of a ship's collider, in sprite pixels before scaling.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum ColliderShape {
+    Circle { radius: f32 },
+    Capsule { radius: f32, length: f32 },
+    Rectangle { width: f32, height: f32 },
+}
+
+impl ColliderShape {
+    pub fn collider(self) -> Collider {
+        match self {
+            Self::Circle { radius } => Collider::circle(radius),
+            Self::Capsule { radius, length } => Collider::capsule(radius, length),
+            Self::Rectangle { width, height } => Collider::rectangle(width, height),
+        }
+    }
+}
+
+/// The hull, weapon mount and engine sprites of `ship`, spawned as children of the ship entity.
+/// `engine` is added to the engine sprite to control how it animates.
+pub fn ship_sprites(ship: &ShipDefinition, engine: impl Bundle) -> impl Bundle {
+    let hull = (
+        Sprite {
+            image: ship.hull_sprite.clone(),
+            color: ship.tint,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 2.0),
+    );
+    let weapon_mounts = ship.weapon_mounts.clone().map(|mounts| {
+        (
+            Name::new("Weapon Mounts"),
+            Sprite {
+                color: ship.tint,
+                ..Sprite::from_atlas_image(mounts.image, TextureAtlas::from(mounts.layout))
+            },
+            Transform::from_xyz(0.0, 0.0, 1.0),
+        )
+    });
+    let engine = (
+        Name::new("Engine"),
+        Sprite::from_atlas_image(
+            ship.engine.image.clone(),
+            TextureAtlas::from(ship.engine.layout.clone()),
+        ),
+        Transform::from_xyz(0.0, -0.3, 0.0),
+        engine,
+    );
+
+    Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+        parent.spawn(hull);
+        if let Some(weapon_mounts) = weapon_mounts {
+            parent.spawn(weapon_mounts);
+        }
+        parent.spawn(engine);
+    }))
+}
+
+/// The `.ship.ron` file of every hull the player can choose from, in the order shown on the ship
+/// select screen.
+const HULLS: [&str; 3] = [
+    "ships/fighter.ship.ron",
+    "ships/interceptor.ship.ron",
+    "ships/hauler.ship.ron",
+];
+
+/// Every hull the player can choose from, in the order shown on the ship select screen.
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct HullAssets {
+    #[dependency]
+    pub hulls: Vec<Handle<ShipDefinition>>,
+}
+
+impl FromWorld for HullAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            hulls: HULLS.iter().map(|path| assets.load(*path)).collect(),
+        }
+    }
+}
+
+impl HullAssets {
+    /// The definition of the hull the player has selected.
+    pub fn selected<'a>(
+        &self,
+        selected: SelectedShip,
+        definitions: &'a Assets<ShipDefinition>,
+    ) -> Option<&'a ShipDefinition> {
+        definitions.get(self.hulls.get(selected.0)?)
+    }
+}
+
+/// The ship the player will fly, as an index into [`HullAssets::hulls`].
+#[derive(Resource, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Resource)]
+pub struct SelectedShip(pub usize);
+
+impl SelectedShip {
+    /// The `.ship.ron` file of the selected hull, which unlike its index stays the same when hulls
+    /// are added or reordered.
+    pub fn path(self) -> Option<&'static str> {
+        HULLS.get(self.0).copied()
+    }
+
+    /// The hull loaded from the `.ship.ron` file at `path`.
+    pub fn from_path(path: &str) -> Option<Self> {
+        HULLS.iter().position(|hull| *hull == path).map(Self)
+    }
+}
+
+/// The layout of a `.ship.ron` file. Paths are relative to the `assets` directory.
+#[derive(Deserialize)]
+struct ShipFile {
+    name: String,
+    #[serde(default)]
+    description: String,
+    hull_sprite: String,
+    /// sRGB, defaults to white.
+    #[serde(default = "white")]
+    tint: (f32, f32, f32),
+    #[serde(default = "one")]
+    scale: f32,
+    engine: SpriteSheetFile,
+    #[serde(default)]
+    weapon_mounts: Option<SpriteSheetFile>,
+    collider: ColliderShape,
+    max_speed: f32,
+    thrust: f32,
+    #[serde(default)]
+    reverse_thrust: f32,
+    /// Degrees per second.
+    turn_rate: f32,
+    hull: f32,
+    #[serde(default)]
+    shield: f32,
+    cargo_capacity: u32,
+    #[serde(default)]
+    cargo_overflow: CargoOverflow,
+    hardpoints: Vec<HardpointFile>,
+}
+
+#[derive(Deserialize)]
+struct SpriteSheetFile {
+    path: String,
+    frame_size: UVec2,
+    frames: u32,
+}
+
+#[derive(Deserialize)]
+struct HardpointFile {
+    /// Path to a `.weapon.ron` file.
+    weapon: String,
+    /// Where projectiles spawn relative to the ship's center, before rotation.
+    offset: Vec2,
+}
+
+fn white() -> (f32, f32, f32) {
+    (1.0, 1.0, 1.0)
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+#[derive(Default)]
+struct ShipDefinitionLoader;
+
+impl AssetLoader for ShipDefinitionLoader {
+    type Asset = ShipDefinition;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<ShipDefinition, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: ShipFile = ron::de::from_bytes(&bytes)?;
+
+        let (red, green, blue) = file.tint;
+        Ok(ShipDefinition {
+            name: file.name,
+            description: file.description,
+            hull_sprite: load_pixel_art(load_context, file.hull_sprite),
+            tint: Color::srgb(red, green, blue),
+            scale: file.scale,
+            engine: load_sprite_sheet(load_context, "engine", file.engine),
+            weapon_mounts: file
+                .weapon_mounts
+                .map(|sheet| load_sprite_sheet(load_context, "weapon_mounts", sheet)),
+            collider: file.collider,
+            max_speed: file.max_speed,
+            thrust: file.thrust,
+            reverse_thrust: file.reverse_thrust,
+            turn_rate: file.turn_rate.to_radians(),
+            hull: file.hull,
+            shield: file.shield,
+            cargo_capacity: file.cargo_capacity,
+            cargo_overflow: file.cargo_overflow,
+            hardpoints: file
+                .hardpoints
+                .into_iter()
+                .map(|hardpoint| Hardpoint {
+                    weapon: load_context.load(hardpoint.weapon),
+                    offset: hardpoint.offset,
+                })
+                .collect(),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ship.ron"]
+    }
+}
+
+fn load_pixel_art(load_context: &mut LoadContext, path: String) -> Handle<Image> {
+    load_context
+        .loader()
+        .with_settings(|settings: &mut ImageLoaderSettings| {
+            // Use `nearest` image sampling to preserve pixel art style.
+            settings.sampler = ImageSampler::nearest();
+        })
+        .load(path)
+}
+
+fn load_sprite_sheet(
+    load_context: &mut LoadContext,
+    label: &str,
+    sheet: SpriteSheetFile,
+) -> SpriteSheet {
+    let frames = sheet.frames.max(1);
+    let layout = TextureAtlasLayout::from_grid(sheet.frame_size, frames, 1, None, None);
+    SpriteSheet {
+        image: load_pixel_art(load_context, sheet.path),
+        layout: load_context.add_labeled_asset(label.to_string(), layout),
+        indices: AnimationIndices {
+            first: 0,
+            last: frames as usize - 1,
+        },
+    }
+}
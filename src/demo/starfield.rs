@@ -0,0 +1,265 @@
+//! A procedural parallax backdrop of stars and nebulae behind the level.
+//!
+//! Each layer is a square tile of stars scattered from the sector seed. The tile repeats in every
+//! direction and scrolls at its own rate as the camera moves, so nearer layers slide past faster
+//! than distant ones. Layers also drift slowly on their own, which keeps the backdrop alive when
+//! the camera stands still in screen wrap levels.
+
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{AppSystems, PausableSystems, demo::sector::SectorSeed, screens::Screen};
+
+/// Width and height of a layer's tile. Must cover the view at the camera's furthest zoom.
+const TILE_SIZE: f32 = 4096.0;
+const SPACE_COLOR: Color = Color::srgb(0.020, 0.024, 0.055);
+const NEBULA_COLORS: [Color; 3] = [
+    Color::srgb(0.38, 0.20, 0.62),
+    Color::srgb(0.16, 0.34, 0.64),
+    Color::srgb(0.62, 0.22, 0.36),
+];
+/// Pixel width and height of the generated glow texture.
+const GLOW_TEXTURE_SIZE: u32 = 64;
+
+/// The backdrop layers, from furthest to nearest.
+const LAYERS: [BackdropLayer; 4] = [
+    BackdropLayer {
+        nebula: true,
+        count: 8,
+        parallax: 0.03,
+        min_size: 600.0,
+        max_size: 1400.0,
+        alpha: 0.12,
+        drift: Vec2::new(1.5, 0.5),
+    },
+    BackdropLayer {
+        nebula: false,
+        count: 500,
+        parallax: 0.08,
+        min_size: 2.0,
+        max_size: 4.0,
+        alpha: 0.45,
+        drift: Vec2::new(2.0, 0.8),
+    },
+    BackdropLayer {
+        nebula: false,
+        count: 250,
+        parallax: 0.2,
+        min_size: 3.0,
+        max_size: 6.0,
+        alpha: 0.7,
+        drift: Vec2::new(4.0, 1.5),
+    },
+    BackdropLayer {
+        nebula: false,
+        count: 90,
+        parallax: 0.45,
+        min_size: 5.0,
+        max_size: 9.0,
+        alpha: 1.0,
+        drift: Vec2::new(8.0, 3.0),
+    },
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<StarfieldLayer>();
+    app.register_type::<Star>();
+
+    // nothing to load, the textures are generated
+    app.init_resource::<StarfieldAssets>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_starfield);
+    app.add_systems(
+        Update,
+        drift_starfield
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+    // position the layers once the camera has settled for the frame
+    app.add_systems(
+        PostUpdate,
+        scroll_starfield.before(TransformSystems::Propagate),
+    );
+}
+
+/// How one layer of the backdrop looks and moves.
+struct BackdropLayer {
+    /// Soft, colored clouds rather than stars.
+    nebula: bool,
+    count: usize,
+    /// How much the layer moves with the world, from 0 (infinitely far away, fixed to the
+    /// camera) to 1 (as close as the level itself).
+    parallax: f32,
+    min_size: f32,
+    max_size: f32,
+    alpha: f32,
+    /// World units per second the layer drifts by on its own.
+    drift: Vec2,
+}
+
+#[derive(Resource, Clone)]
+struct StarfieldAssets {
+    /// A soft white dot, fading out from the middle.
+    glow: Handle<Image>,
+}
+
+impl FromWorld for StarfieldAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut images = world.resource_mut::<Assets<Image>>();
+        Self {
+            glow: images.add(glow_image()),
+        }
+    }
+}
+
+fn glow_image() -> Image {
+    let size = GLOW_TEXTURE_SIZE;
+    let half = size as f32 / 2.0;
+    let data = (0..size * size)
+        .flat_map(|i| {
+            let pixel = Vec2::new((i % size) as f32, (i / size) as f32) + 0.5;
+            let falloff = (1.0 - pixel.distance(Vec2::splat(half)) / half).max(0.0);
+            let alpha = (falloff * falloff * 255.0) as u8;
+            [255, 255, 255, alpha]
+        })
+        .collect();
+
+    Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// A layer of the backdrop, kept centered on the camera.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+#[require(Transform, Visibility)]
+struct StarfieldLayer {
+    parallax: f32,
+    drift: Vec2,
+    /// How far the layer has drifted so far, wrapped to the tile.
+    drifted: Vec2,
+}
+
+/// A star or nebula cloud, at a fixed place within its layer's tile.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct Star(Vec2);
+
+fn spawn_starfield(
+    mut commands: Commands,
+    sector_seed: Res<SectorSeed>,
+    starfield_assets: Res<StarfieldAssets>,
+) {
+    let root = commands
+        .spawn((
+            Name::new("Starfield"),
+            Transform::default(),
+            Visibility::default(),
+            DespawnOnExit(Screen::Gameplay),
+        ))
+        .id();
+
+    for (index, layer) in LAYERS.iter().enumerate() {
+        // give each layer its own stream, so adding stars to one doesn't move the others
+        let mut rng = ChaCha8Rng::seed_from_u64(sector_seed.seed);
+        rng.set_stream(index as u64 + 1);
+
+        let z = -100.0 + index as f32;
+        let mut entity = commands.spawn((
+            Name::new(if layer.nebula {
+                "Nebula Layer"
+            } else {
+                "Star Layer"
+            }),
+            StarfieldLayer {
+                parallax: layer.parallax,
+                drift: layer.drift,
+                drifted: Vec2::ZERO,
+            },
+            Transform::from_xyz(0.0, 0.0, z),
+            ChildOf(root),
+        ));
+
+        entity.with_children(|parent| {
+            // the furthest layer carries the backdrop color
+            if index == 0 {
+                parent.spawn((
+                    Name::new("Space"),
+                    Sprite::from_color(SPACE_COLOR, Vec2::splat(TILE_SIZE * 2.0)),
+                    Transform::from_xyz(0.0, 0.0, -1.0),
+                ));
+            }
+
+            for _ in 0..layer.count {
+                let position = Vec2::new(
+                    rng.random_range(0.0..TILE_SIZE),
+                    rng.random_range(0.0..TILE_SIZE),
+                );
+                let size = rng.random_range(layer.min_size..=layer.max_size);
+                let color = if layer.nebula {
+                    *NEBULA_COLORS.choose(&mut rng).unwrap_or(&Color::WHITE)
+                } else {
+                    // tint some stars slightly blue
+                    Color::srgb(
+                        rng.random_range(0.8..=1.0),
+                        rng.random_range(0.85..=1.0),
+                        1.0,
+                    )
+                };
+
+                parent.spawn((
+                    Star(position),
+                    Sprite {
+                        image: starfield_assets.glow.clone(),
+                        color: color.with_alpha(layer.alpha * rng.random_range(0.5..=1.0)),
+                        custom_size: Some(Vec2::splat(size)),
+                        ..default()
+                    },
+                    Transform::default(),
+                ));
+            }
+        });
+    }
+}
+
+fn drift_starfield(time: Res<Time>, mut layers: Query<&mut StarfieldLayer>) {
+    for mut layer in &mut layers {
+        let drifted = layer.drifted + layer.drift * time.delta_secs();
+        layer.drifted = drifted.rem_euclid(Vec2::splat(TILE_SIZE));
+    }
+}
+
+/// Keep each layer centered on the camera, and slide its stars around the repeating tile by the
+/// layer's share of the camera's movement.
+fn scroll_starfield(
+    camera: Single<&Transform, (With<Camera2d>, Without<StarfieldLayer>, Without<Star>)>,
+    mut layers: Query<(&StarfieldLayer, &Children, &mut Transform), Without<Star>>,
+    mut stars: Query<(&Star, &mut Transform), Without<StarfieldLayer>>,
+) {
+    let camera_position = camera.translation.xy();
+    let tile = Vec2::splat(TILE_SIZE);
+
+    for (layer, children, mut transform) in &mut layers {
+        transform.translation = camera_position.extend(transform.translation.z);
+
+        let scroll = layer.drifted - camera_position * layer.parallax;
+        let mut stars = stars.iter_many_mut(children);
+        while let Some((star, mut star_transform)) = stars.fetch_next() {
+            let position = (star.0 + scroll + tile / 2.0).rem_euclid(tile) - tile / 2.0;
+            star_transform.translation = position.extend(0.0);
+        }
+    }
+}
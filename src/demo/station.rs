@@ -0,0 +1,400 @@
+//! A space station the player can dock at to sell ore, repair, restock ammo and refit their ship.
+//!
+//! Flying into the station's docking zone pauses the game and opens the station menu. The
+//! services themselves live here, so the menu only has to lay out buttons for them.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems, Pause,
+    demo::{
+        combat::{Health, Shield},
+        ore::Cargo,
+        player::Player,
+        radar::PointOfInterest,
+        respawn::Respawning,
+        shapes::ShapeAssets,
+        weapon::{Weapon, WeaponDefinition},
+    },
+    menus::Menu,
+    screens::Screen,
+};
+
+const STATION_RADIUS: f32 = 64.0;
+const DOCKING_RADIUS: f32 = 140.0;
+const STATION_COLOR: Color = Color::srgb(0.62, 0.66, 0.72);
+const DOCKING_ZONE_COLOR: Color = Color::srgba(0.384, 0.600, 0.820, 0.15);
+/// Credits per point of hull repaired.
+const REPAIR_PRICE: u32 = 10;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Station>();
+    app.register_type::<Credits>();
+    app.init_resource::<Credits>();
+
+    app.add_systems(
+        Update,
+        dock_at_station
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Money earned by selling ore, spent on services at a station and on [upgrades](super::upgrades).
+/// Kept between runs in the player's save slot.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Credits(pub u32);
+
+impl Credits {
+    /// Take `price` credits if there are enough, returning whether the purchase went through.
+    pub fn spend(&mut self, price: u32) -> bool {
+        let Some(left) = self.0.checked_sub(price) else {
+            return false;
+        };
+        self.0 = left;
+        true
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+#[require(Visibility, RigidBody::Static)]
+pub struct Station {
+    /// Whether the player was in the docking zone last frame. The station only docks ships that
+    /// fly in, so undocking doesn't immediately dock the player again.
+    player_in_zone: bool,
+}
+
+/// A station at `position`, with its docking zone drawn around it.
+pub fn station(position: Vec2, shape_assets: &ShapeAssets) -> impl Bundle {
+    (
+        Name::new("Station"),
+        Station {
+            // don't dock a player who happens to start inside the zone
+            player_in_zone: true,
+        },
+        PointOfInterest,
+        DespawnOnExit(Screen::Gameplay),
+        Collider::circle(STATION_RADIUS),
+        Sprite {
+            image: shape_assets.circle.clone(),
+            color: STATION_COLOR,
+            custom_size: Some(Vec2::splat(STATION_RADIUS * 2.0)),
+            ..default()
+        },
+        Transform::from_translation(position.extend(0.0)),
+        children![(
+            Name::new("Docking Zone"),
+            Sprite {
+                image: shape_assets.circle.clone(),
+                color: DOCKING_ZONE_COLOR,
+                custom_size: Some(Vec2::splat(DOCKING_RADIUS * 2.0)),
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, -1.0),
+        )],
+    )
+}
+
+/// Open the station menu when the player flies into a docking zone.
+fn dock_at_station(
+    mut stations: Query<(&mut Station, &Transform)>,
+    player: Single<
+        (&Transform, &mut LinearVelocity, &mut AngularVelocity),
+        (With<Player>, Without<Respawning>, Without<Station>),
+    >,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    let (player_transform, mut velocity, mut angular_velocity) = player.into_inner();
+    let player_position = player_transform.translation.xy();
+
+    for (mut station, transform) in &mut stations {
+        let in_zone = transform.translation.xy().distance(player_position) <= DOCKING_RADIUS;
+        let arrived = in_zone && !station.player_in_zone;
+        station.player_in_zone = in_zone;
+
+        if arrived {
+            // come to a stop, so the ship doesn't drift off once it undocks
+            velocity.0 = Vec2::ZERO;
+            angular_velocity.0 = 0.0;
+            next_pause.set(Pause(true));
+            next_menu.set(Menu::Station);
+        }
+    }
+}
+
+/// What the ore in the hold would sell for.
+pub fn cargo_value(cargo: &Cargo) -> u32 {
+    cargo
+        .ore
+        .iter()
+        .map(|(kind, amount)| kind.price() * amount)
+        .sum()
+}
+
+/// Sell all the ore in the hold, returning the credits earned.
+pub fn sell_cargo(cargo: &mut Cargo, credits: &mut Credits) -> u32 {
+    let value = cargo_value(cargo);
+    cargo.ore.clear();
+    credits.0 += value;
+    value
+}
+
+/// What it costs to repair the hull fully.
+pub fn repair_price(health: &Health) -> u32 {
+    (health.max - health.current).max(0.0).ceil() as u32 * REPAIR_PRICE
+}
+
+/// Repair as much of the hull as the player can afford, and top up the shield for free.
+pub fn repair_hull(health: &mut Health, shield: Option<&mut Shield>, credits: &mut Credits) {
+    let affordable = (credits.0 / REPAIR_PRICE) as f32;
+    let repaired = (health.max - health.current).min(affordable).max(0.0);
+    if credits.spend(repaired.ceil() as u32 * REPAIR_PRICE) {
+        health.current += repaired;
+    }
+    if let Some(shield) = shield {
+        shield.current = shield.max;
+    }
+}
+
+/// What it costs to restock every weapon to its starting ammo.
+pub fn restock_price(weapon: &Weapon, definitions: &Assets<WeaponDefinition>) -> u32 {
+    weapon
+        .loadout
+        .iter()
+        .filter_map(|slot| {
+            let definition = definitions.get(&slot.definition)?;
+            let missing = definition.ammo?.saturating_sub(slot.ammo?);
+            Some(missing * definition.ammo_price)
+        })
+        .sum()
+}
+
+/// Restock as much ammo as the player can afford, weapon by weapon.
+pub fn restock_ammo(
+    weapon: &mut Weapon,
+    definitions: &Assets<WeaponDefinition>,
+    credits: &mut Credits,
+) {
+    for slot in &mut weapon.loadout {
+        let Some(definition) = definitions.get(&slot.definition) else {
+            continue;
+        };
+        let (Some(max), Some(ammo)) = (definition.ammo, slot.ammo.as_mut()) else {
+            continue;
+        };
+
+        let missing = max.saturating_sub(*ammo);
+        let affordable = credits
+            .0
+            .checked_div(definition.ammo_price)
+            .unwrap_or(missing);
+        let bought = missing.min(affordable);
+        if credits.spend(bought * definition.ammo_price) {
+            *ammo += bought;
+        }
+    }
+}
+
+/// A permanent improvement to the ship for the rest of the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Refit {
+    ReinforcedHull,
+    ShieldCapacitor,
+    ExpandedHold,
+}
+
+impl Refit {
+    pub const ALL: [Self; 3] = [
+        Self::ReinforcedHull,
+        Self::ShieldCapacitor,
+        Self::ExpandedHold,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ReinforcedHull => "Reinforced hull (+2 hull)",
+            Self::ShieldCapacitor => "Shield capacitor (+2 shield)",
+            Self::ExpandedHold => "Expanded hold (+10 cargo)",
+        }
+    }
+
+    pub fn price(self) -> u32 {
+        match self {
+            Self::ReinforcedHull => 150,
+            Self::ShieldCapacitor => 150,
+            Self::ExpandedHold => 100,
+        }
+    }
+
+    /// Fit the refit to the player's ship, if they can afford it.
+    pub fn buy(
+        self,
+        credits: &mut Credits,
+        health: &mut Health,
+        shield: Option<&mut Shield>,
+        cargo: &mut Cargo,
+    ) -> bool {
+        // a shield capacitor is no use to a ship without a shield
+        if self == Self::ShieldCapacitor && shield.is_none() {
+            return false;
+        }
+        if !credits.spend(self.price()) {
+            return false;
+        }
+
+        match self {
+            Self::ReinforcedHull => {
+                health.max += 2.0;
+                health.current += 2.0;
+            }
+            Self::ShieldCapacitor => {
+                if let Some(shield) = shield {
+                    shield.max += 2.0;
+                    shield.current = shield.max;
+                }
+            }
+            Self::ExpandedHold => cargo.capacity += 10,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::demo::{
+        ore::OreKind,
+        weapon::{ProjectileSprite, WeaponSlot},
+    };
+
+    use super::*;
+
+    fn weapon_definition(ammo: Option<u32>, ammo_price: u32) -> WeaponDefinition {
+        WeaponDefinition {
+            name: "Test".to_string(),
+            fire_rate: 1.0,
+            spread: 0.0,
+            projectile_count: 1,
+            projectile_speed: 1.0,
+            lifetime: 1.0,
+            damage: 1.0,
+            ammo,
+            ammo_price,
+            sprite: ProjectileSprite {
+                image: Handle::default(),
+                atlas: None,
+                scale: 1.0,
+                collider_radius: 1.0,
+            },
+            sound: None,
+            homing: None,
+            blast_radius: None,
+        }
+    }
+
+    fn weapon(slots: impl IntoIterator<Item = (Handle<WeaponDefinition>, Option<u32>)>) -> Weapon {
+        Weapon {
+            loadout: slots
+                .into_iter()
+                .map(|(definition, ammo)| WeaponSlot {
+                    definition,
+                    ammo,
+                    offset: Vec2::ZERO,
+                })
+                .collect(),
+            ..Weapon::new(&[], &Assets::default())
+        }
+    }
+
+    #[test]
+    fn sell_cargo_empties_the_hold() {
+        let mut cargo = Cargo::new(20);
+        cargo.add(OreKind::Iron, 3);
+        cargo.add(OreKind::Platinum, 2);
+        let mut credits = Credits(10);
+
+        let value = 3 * OreKind::Iron.price() + 2 * OreKind::Platinum.price();
+        assert_eq!(sell_cargo(&mut cargo, &mut credits), value);
+        assert_eq!(credits, Credits(10 + value));
+        assert_eq!(cargo.total(), 0);
+    }
+
+    #[test]
+    fn repair_hull_repairs_fully_when_affordable() {
+        let mut health = Health {
+            current: 2.5,
+            max: 10.0,
+        };
+        let mut shield = Shield::new(4.0, 1.0, 1.0);
+        shield.current = 1.0;
+        let mut credits = Credits(1000);
+
+        assert_eq!(repair_price(&health), 8 * REPAIR_PRICE);
+        repair_hull(&mut health, Some(&mut shield), &mut credits);
+        assert_eq!(health.current, health.max);
+        assert_eq!(shield.current, shield.max);
+        assert_eq!(credits, Credits(1000 - 8 * REPAIR_PRICE));
+    }
+
+    #[test]
+    fn repair_hull_repairs_what_the_player_can_afford() {
+        let mut health = Health {
+            current: 2.0,
+            max: 10.0,
+        };
+        let mut credits = Credits(3 * REPAIR_PRICE + 5);
+
+        repair_hull(&mut health, None, &mut credits);
+        assert_eq!(health.current, 5.0);
+        assert_eq!(credits, Credits(5));
+    }
+
+    #[test]
+    fn restock_ammo_buys_what_the_player_can_afford() {
+        let mut definitions = Assets::default();
+        let rockets = definitions.add(weapon_definition(Some(10), 5));
+        let cannon = definitions.add(weapon_definition(None, 0));
+        let mut weapon = weapon([(cannon, None), (rockets, Some(4))]);
+        let mut credits = Credits(22);
+
+        assert_eq!(restock_price(&weapon, &definitions), 30);
+        restock_ammo(&mut weapon, &definitions, &mut credits);
+        assert_eq!(weapon.loadout[0].ammo, None);
+        assert_eq!(weapon.loadout[1].ammo, Some(8));
+        assert_eq!(credits, Credits(2));
+    }
+
+    #[test]
+    fn restock_ammo_is_free_for_weapons_without_an_ammo_price() {
+        let mut definitions = Assets::default();
+        let torpedoes = definitions.add(weapon_definition(Some(4), 0));
+        let mut weapon = weapon([(torpedoes, Some(0))]);
+        let mut credits = Credits(0);
+
+        restock_ammo(&mut weapon, &definitions, &mut credits);
+        assert_eq!(weapon.loadout[0].ammo, Some(4));
+        assert_eq!(credits, Credits(0));
+    }
+
+    #[test]
+    fn refits_need_credits_and_a_shield_to_fit() {
+        let mut health = Health::new(10.0);
+        let mut cargo = Cargo::new(20);
+
+        let mut credits = Credits(Refit::ShieldCapacitor.price());
+        assert!(!Refit::ShieldCapacitor.buy(&mut credits, &mut health, None, &mut cargo));
+        assert_eq!(credits, Credits(Refit::ShieldCapacitor.price()));
+
+        let mut credits = Credits(Refit::ExpandedHold.price() - 1);
+        assert!(!Refit::ExpandedHold.buy(&mut credits, &mut health, None, &mut cargo));
+        assert_eq!(cargo.capacity, 20);
+
+        let mut credits = Credits(Refit::ExpandedHold.price());
+        assert!(Refit::ExpandedHold.buy(&mut credits, &mut health, None, &mut cargo));
+        assert_eq!(cargo.capacity, 30);
+        assert_eq!(credits, Credits(0));
+    }
+}
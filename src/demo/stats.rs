@@ -0,0 +1,118 @@
+//! Statistics about the current run, shown on the game over screen.
+
+use std::time::Duration;
+
+use bevy::{platform::collections::HashSet, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        asteroid::AsteroidDestroyed, combat::DamageDealt, enemy::EnemyDestroyed, ore::OreCollected,
+        player::Player, score::Score, weapon::Projectile,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<RunStats>();
+    app.init_resource::<RunStats>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), reset_run_stats);
+    app.add_systems(
+        Update,
+        (
+            track_time_survived.in_set(AppSystems::TickTimers),
+            (
+                track_score,
+                count_asteroids_destroyed,
+                count_enemies_destroyed,
+                count_ore_mined,
+                count_shots,
+            )
+                .in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// What the player achieved during the current (or last) run.
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct RunStats {
+    pub score: u32,
+    pub ore_mined: u32,
+    pub asteroids_destroyed: u32,
+    pub enemies_destroyed: u32,
+    pub shots_fired: u32,
+    /// Shots that damaged something.
+    pub shots_hit: u32,
+    /// Time spent in the level, not counting time paused.
+    pub time_survived: Duration,
+}
+
+impl RunStats {
+    /// Fraction of shots fired that hit something, or `None` if no shots were fired.
+    pub fn accuracy(&self) -> Option<f32> {
+        (self.shots_fired > 0).then(|| self.shots_hit as f32 / self.shots_fired as f32)
+    }
+}
+
+fn reset_run_stats(mut commands: Commands) {
+    commands.insert_resource(RunStats::default());
+}
+
+fn track_time_survived(time: Res<Time>, mut stats: ResMut<RunStats>) {
+    stats.time_survived += time.delta();
+}
+
+fn track_score(score: Single<&Score, (With<Player>, Changed<Score>)>, mut stats: ResMut<RunStats>) {
+    stats.score = score.0;
+}
+
+fn count_asteroids_destroyed(
+    player: Single<Entity, With<Player>>,
+    mut destroyed: MessageReader<AsteroidDestroyed>,
+    mut stats: ResMut<RunStats>,
+) {
+    let player = *player;
+    stats.asteroids_destroyed += destroyed
+        .read()
+        .filter(|asteroid| asteroid.destroyed_by == Some(player))
+        .count() as u32;
+}
+
+fn count_enemies_destroyed(
+    mut destroyed: MessageReader<EnemyDestroyed>,
+    mut stats: ResMut<RunStats>,
+) {
+    stats.enemies_destroyed += destroyed.read().count() as u32;
+}
+
+fn count_ore_mined(mut ore_collected: MessageReader<OreCollected>, mut stats: ResMut<RunStats>) {
+    for collected in ore_collected.read() {
+        stats.ore_mined += collected.amount;
+    }
+}
+
+fn count_shots(
+    player: Single<Entity, With<Player>>,
+    fired: Query<&Projectile, Added<Projectile>>,
+    mut damage_dealt: MessageReader<DamageDealt>,
+    mut stats: ResMut<RunStats>,
+) {
+    let player = *player;
+    stats.shots_fired += fired
+        .iter()
+        .filter(|projectile| projectile.shooter == player)
+        .count() as u32;
+    // a blast damages everything in range, but it's still only one shot
+    let hits: HashSet<Entity> = damage_dealt
+        .read()
+        .filter(|hit| hit.source == player)
+        .filter_map(|hit| hit.projectile)
+        .collect();
+    stats.shots_hit += hits.len() as u32;
+}
@@ -0,0 +1,343 @@
+//! Permanent ship upgrades, bought with credits and kept between runs.
+//!
+//! Upgrades form a tree: each node needs the nodes before it in its branch, and some need nodes
+//! from another branch too. Bought upgrades add up to a set of [`ShipModifiers`], which are
+//! applied on top of the ship's definition when the player's ship is spawned.
+//!
+//! Upgrades are kept in the player's save slot, see [`save`](super::save).
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::demo::station::Credits;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Upgrades>();
+    app.init_resource::<Upgrades>();
+}
+
+/// A node in the upgrade tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum Upgrade {
+    Thrusters1,
+    Thrusters2,
+    Thrusters3,
+    Gyros1,
+    Gyros2,
+    Hold1,
+    Hold2,
+    Hold3,
+    Munitions1,
+    Munitions2,
+    Autoloader1,
+    Autoloader2,
+    Plating1,
+    Plating2,
+    Plating3,
+}
+
+/// What an upgrade improves, and by how much.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpgradeEffect {
+    /// Fraction added to the ship's thrust.
+    Thrust(f32),
+    /// Fraction added to the ship's turn rate.
+    TurnRate(f32),
+    /// Units of ore added to the hold.
+    Cargo(u32),
+    /// Fraction added to the damage of every weapon.
+    Damage(f32),
+    /// Fraction added to the shots per second of every weapon.
+    FireRate(f32),
+    /// Points added to the hull.
+    Hull(f32),
+}
+
+impl UpgradeEffect {
+    pub fn label(self) -> String {
+        match self {
+            Self::Thrust(bonus) => format!("+{:.0}% thrust", 100.0 * bonus),
+            Self::TurnRate(bonus) => format!("+{:.0}% turn rate", 100.0 * bonus),
+            Self::Cargo(bonus) => format!("+{bonus} cargo"),
+            Self::Damage(bonus) => format!("+{:.0}% damage", 100.0 * bonus),
+            Self::FireRate(bonus) => format!("+{:.0}% fire rate", 100.0 * bonus),
+            Self::Hull(bonus) => format!("+{bonus} hull"),
+        }
+    }
+}
+
+impl Upgrade {
+    /// The branches of the tree and their nodes, in the order they are bought.
+    pub const BRANCHES: [(&'static str, &'static [Self]); 4] = [
+        (
+            "Engines",
+            &[
+                Self::Thrusters1,
+                Self::Thrusters2,
+                Self::Thrusters3,
+                Self::Gyros1,
+                Self::Gyros2,
+            ],
+        ),
+        ("Hold", &[Self::Hold1, Self::Hold2, Self::Hold3]),
+        (
+            "Weapons",
+            &[
+                Self::Munitions1,
+                Self::Munitions2,
+                Self::Autoloader1,
+                Self::Autoloader2,
+            ],
+        ),
+        ("Hull", &[Self::Plating1, Self::Plating2, Self::Plating3]),
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Thrusters1 => "Thrusters I",
+            Self::Thrusters2 => "Thrusters II",
+            Self::Thrusters3 => "Thrusters III",
+            Self::Gyros1 => "Gyros I",
+            Self::Gyros2 => "Gyros II",
+            Self::Hold1 => "Hold I",
+            Self::Hold2 => "Hold II",
+            Self::Hold3 => "Hold III",
+            Self::Munitions1 => "Munitions I",
+            Self::Munitions2 => "Munitions II",
+            Self::Autoloader1 => "Autoloader I",
+            Self::Autoloader2 => "Autoloader II",
+            Self::Plating1 => "Plating I",
+            Self::Plating2 => "Plating II",
+            Self::Plating3 => "Plating III",
+        }
+    }
+
+    pub fn price(self) -> u32 {
+        match self {
+            Self::Thrusters1 | Self::Gyros1 => 200,
+            Self::Thrusters2 => 500,
+            Self::Thrusters3 => 1000,
+            Self::Gyros2 => 600,
+            Self::Hold1 => 150,
+            Self::Hold2 => 400,
+            Self::Hold3 => 900,
+            Self::Munitions1 | Self::Autoloader1 => 300,
+            Self::Munitions2 | Self::Autoloader2 => 800,
+            Self::Plating1 => 250,
+            Self::Plating2 => 600,
+            Self::Plating3 => 1200,
+        }
+    }
+
+    pub fn effect(self) -> UpgradeEffect {
+        match self {
+            Self::Thrusters1 | Self::Thrusters2 => UpgradeEffect::Thrust(0.15),
+            Self::Thrusters3 => UpgradeEffect::Thrust(0.2),
+            Self::Gyros1 => UpgradeEffect::TurnRate(0.15),
+            Self::Gyros2 => UpgradeEffect::TurnRate(0.2),
+            Self::Hold1 => UpgradeEffect::Cargo(10),
+            Self::Hold2 => UpgradeEffect::Cargo(15),
+            Self::Hold3 => UpgradeEffect::Cargo(25),
+            Self::Munitions1 => UpgradeEffect::Damage(0.15),
+            Self::Munitions2 => UpgradeEffect::Damage(0.2),
+            Self::Autoloader1 => UpgradeEffect::FireRate(0.15),
+            Self::Autoloader2 => UpgradeEffect::FireRate(0.2),
+            Self::Plating1 => UpgradeEffect::Hull(2.0),
+            Self::Plating2 => UpgradeEffect::Hull(3.0),
+            Self::Plating3 => UpgradeEffect::Hull(5.0),
+        }
+    }
+
+    /// The upgrades that must be bought before this one.
+    pub fn requires(self) -> &'static [Self] {
+        match self {
+            Self::Thrusters1 | Self::Hold1 | Self::Munitions1 | Self::Plating1 => &[],
+            Self::Thrusters2 => &[Self::Thrusters1],
+            Self::Thrusters3 => &[Self::Thrusters2],
+            Self::Gyros1 => &[Self::Thrusters1],
+            Self::Gyros2 => &[Self::Gyros1, Self::Thrusters2],
+            Self::Hold2 => &[Self::Hold1],
+            Self::Hold3 => &[Self::Hold2],
+            Self::Munitions2 => &[Self::Munitions1],
+            Self::Autoloader1 => &[Self::Munitions1],
+            Self::Autoloader2 => &[Self::Autoloader1, Self::Munitions2],
+            Self::Plating2 => &[Self::Plating1],
+            Self::Plating3 => &[Self::Plating2],
+        }
+    }
+}
+
+/// Where an upgrade stands for the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeState {
+    /// Some of the upgrades it requires haven't been bought yet.
+    Locked,
+    /// Available to buy.
+    Unlocked,
+    Purchased,
+}
+
+/// The upgrades the player has bought.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Upgrades {
+    /// In the order they were bought.
+    purchased: Vec<Upgrade>,
+}
+
+impl Upgrades {
+    pub fn new(purchased: Vec<Upgrade>) -> Self {
+        Self { purchased }
+    }
+
+    /// In the order they were bought.
+    pub fn purchased(&self) -> &[Upgrade] {
+        &self.purchased
+    }
+
+    pub fn state(&self, upgrade: Upgrade) -> UpgradeState {
+        if self.purchased.contains(&upgrade) {
+            UpgradeState::Purchased
+        } else if upgrade
+            .requires()
+            .iter()
+            .all(|required| self.purchased.contains(required))
+        {
+            UpgradeState::Unlocked
+        } else {
+            UpgradeState::Locked
+        }
+    }
+
+    /// Buy `upgrade` if it's unlocked and the player can afford it, returning whether it was
+    /// bought.
+    pub fn buy(&mut self, upgrade: Upgrade, credits: &mut Credits) -> bool {
+        if self.state(upgrade) != UpgradeState::Unlocked || !credits.spend(upgrade.price()) {
+            return false;
+        }
+        self.purchased.push(upgrade);
+        true
+    }
+
+    /// The combined effect of every bought upgrade.
+    pub fn modifiers(&self) -> ShipModifiers {
+        let mut modifiers = ShipModifiers::default();
+        for upgrade in &self.purchased {
+            match upgrade.effect() {
+                UpgradeEffect::Thrust(bonus) => modifiers.thrust += bonus,
+                UpgradeEffect::TurnRate(bonus) => modifiers.turn_rate += bonus,
+                UpgradeEffect::Cargo(bonus) => modifiers.cargo += bonus,
+                UpgradeEffect::Damage(bonus) => modifiers.damage += bonus,
+                UpgradeEffect::FireRate(bonus) => modifiers.fire_rate += bonus,
+                UpgradeEffect::Hull(bonus) => modifiers.hull += bonus,
+            }
+        }
+        modifiers
+    }
+}
+
+/// Changes to a ship's stats from its upgrades. Multipliers start at 1, bonuses at 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShipModifiers {
+    pub thrust: f32,
+    pub turn_rate: f32,
+    pub cargo: u32,
+    pub damage: f32,
+    pub fire_rate: f32,
+    pub hull: f32,
+}
+
+impl Default for ShipModifiers {
+    fn default() -> Self {
+        Self {
+            thrust: 1.0,
+            turn_rate: 1.0,
+            cargo: 0,
+            damage: 1.0,
+            fire_rate: 1.0,
+            hull: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roots_start_unlocked_and_the_rest_locked() {
+        let upgrades = Upgrades::default();
+        for (_, branch) in Upgrade::BRANCHES {
+            assert_eq!(upgrades.state(branch[0]), UpgradeState::Unlocked);
+        }
+        assert_eq!(upgrades.state(Upgrade::Thrusters2), UpgradeState::Locked);
+        assert_eq!(upgrades.state(Upgrade::Gyros2), UpgradeState::Locked);
+    }
+
+    #[test]
+    fn every_upgrade_is_reachable_through_its_requirements() {
+        // buy everything in branch order, which has to work for the tree to be completable
+        let mut upgrades = Upgrades::default();
+        let mut credits = Credits(u32::MAX);
+        let mut remaining: Vec<_> = Upgrade::BRANCHES
+            .iter()
+            .flat_map(|(_, branch)| branch.iter().copied())
+            .collect();
+        while !remaining.is_empty() {
+            let before = remaining.len();
+            remaining.retain(|upgrade| !upgrades.buy(*upgrade, &mut credits));
+            assert!(remaining.len() < before, "stuck on {remaining:?}");
+        }
+    }
+
+    #[test]
+    fn buy_needs_every_requirement() {
+        let mut upgrades = Upgrades::new(vec![Upgrade::Thrusters1, Upgrade::Gyros1]);
+        let mut credits = Credits(10_000);
+
+        // gyros II also needs thrusters II
+        assert!(!upgrades.buy(Upgrade::Gyros2, &mut credits));
+        assert_eq!(credits, Credits(10_000));
+
+        assert!(upgrades.buy(Upgrade::Thrusters2, &mut credits));
+        assert_eq!(upgrades.state(Upgrade::Gyros2), UpgradeState::Unlocked);
+        assert!(upgrades.buy(Upgrade::Gyros2, &mut credits));
+        assert_eq!(
+            credits,
+            Credits(10_000 - Upgrade::Thrusters2.price() - Upgrade::Gyros2.price())
+        );
+    }
+
+    #[test]
+    fn buy_needs_enough_credits_and_only_buys_once() {
+        let mut upgrades = Upgrades::default();
+
+        let mut credits = Credits(Upgrade::Hold1.price() - 1);
+        assert!(!upgrades.buy(Upgrade::Hold1, &mut credits));
+        assert_eq!(upgrades.state(Upgrade::Hold1), UpgradeState::Unlocked);
+
+        let mut credits = Credits(2 * Upgrade::Hold1.price());
+        assert!(upgrades.buy(Upgrade::Hold1, &mut credits));
+        assert_eq!(upgrades.state(Upgrade::Hold1), UpgradeState::Purchased);
+        assert!(!upgrades.buy(Upgrade::Hold1, &mut credits));
+        assert_eq!(credits, Credits(Upgrade::Hold1.price()));
+        assert_eq!(upgrades.purchased(), [Upgrade::Hold1]);
+    }
+
+    #[test]
+    fn modifiers_add_up_bought_upgrades() {
+        let upgrades = Upgrades::new(vec![
+            Upgrade::Thrusters1,
+            Upgrade::Thrusters2,
+            Upgrade::Hold1,
+            Upgrade::Hold2,
+            Upgrade::Plating1,
+        ]);
+        let modifiers = upgrades.modifiers();
+        assert!((modifiers.thrust - 1.3).abs() < 1e-6);
+        assert_eq!(modifiers.cargo, 25);
+        assert_eq!(modifiers.hull, 2.0);
+        assert_eq!(modifiers.damage, 1.0);
+        assert_eq!(modifiers.turn_rate, 1.0);
+    }
+}
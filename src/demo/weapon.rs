@@ -1,26 +1,248 @@
-use super::player::ShipAssets;
-use crate::{AppSystems, PausableSystems, screens::Screen};
+use super::{
+    animation::{AnimationIndices, looping_animation},
+    combat::{GameLayer, Health},
+};
+use crate::{AppSystems, PausableSystems, audio::sound_effect, screens::Screen};
 use avian2d::prelude::*;
-use bevy::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    image::{ImageLoaderSettings, ImageSampler},
+    prelude::*,
+};
+use serde::Deserialize;
+use std::f32::consts::FRAC_PI_2;
 
-const PROJECTILE_SPEED: f32 = 500.0;
-const PROJECTILE_FORWARD_SPAWN_SCALAR: f32 = 30.0;
-const PROJECTILE_DESPAWN_TIME_SECONDS: f32 = 2.0;
-const WEAPON_FIRE_RATE: f32 = 0.16;
+/// How far away a homing projectile can pick up a target.
+const HOMING_RANGE: f32 = 400.0;
+const PROJECTILE_ANIMATION_FPS: f32 = 12.0;
 
 #[derive(Message)]
 pub struct FireWeapon {
     pub entity: Entity,
 }
 
+/// The stats of a kind of weapon, loaded from a `.weapon.ron` file under `assets/weapons` so they
+/// can be tuned without recompiling.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct WeaponDefinition {
+    pub name: String,
+    /// Seconds between shots.
+    pub fire_rate: f32,
+    /// Angle in radians that the projectiles of a single shot are fanned out across.
+    pub spread: f32,
+    /// Projectiles fired per shot.
+    pub projectile_count: u32,
+    pub projectile_speed: f32,
+    /// Seconds before a projectile that hasn't hit anything despawns.
+    pub lifetime: f32,
+    /// Damage dealt by each projectile.
+    pub damage: f32,
+    /// Shots the weapon starts with, or `None` if it never runs out.
+    pub ammo: Option<u32>,
+    /// Credits per shot when restocking ammo at a station.
+    pub ammo_price: u32,
+    pub sprite: ProjectileSprite,
+    /// Played each time the weapon fires.
+    pub sound: Option<Handle<AudioSource>>,
+    /// How fast the projectile turns towards the nearest target, in radians per second.
+    pub homing: Option<f32>,
+    /// Radius around the impact that takes the full damage as well as whatever was hit.
+    pub blast_radius: Option<f32>,
+}
+
+/// How a weapon's projectiles look, and how big they are.
+#[derive(Debug, Clone)]
+pub struct ProjectileSprite {
+    pub image: Handle<Image>,
+    /// Set for animated projectiles, whose image is a horizontal strip of frames.
+    pub atlas: Option<(Handle<TextureAtlasLayout>, AnimationIndices)>,
+    pub scale: f32,
+    /// Collider radius, in image pixels before scaling.
+    pub collider_radius: f32,
+}
+
+/// The layout of a `.weapon.ron` file. Paths are relative to the `assets` directory.
+#[derive(Deserialize)]
+struct WeaponFile {
+    name: String,
+    fire_rate: f32,
+    /// Degrees.
+    #[serde(default)]
+    spread: f32,
+    #[serde(default = "one")]
+    projectile_count: u32,
+    projectile_speed: f32,
+    lifetime: f32,
+    damage: f32,
+    #[serde(default)]
+    ammo: Option<u32>,
+    #[serde(default)]
+    ammo_price: u32,
+    sprite: SpriteFile,
+    #[serde(default)]
+    sound: Option<String>,
+    #[serde(default)]
+    homing: Option<f32>,
+    #[serde(default)]
+    blast_radius: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct SpriteFile {
+    path: String,
+    /// Size of a single animation frame, if the image is a sprite sheet.
+    #[serde(default)]
+    frame_size: Option<UVec2>,
+    #[serde(default = "one")]
+    frames: u32,
+    #[serde(default = "one_f32")]
+    scale: f32,
+    collider_radius: f32,
+}
+
+fn one() -> u32 {
+    1
+}
+
+fn one_f32() -> f32 {
+    1.0
+}
+
+#[derive(Default)]
+struct WeaponDefinitionLoader;
+
+impl AssetLoader for WeaponDefinitionLoader {
+    type Asset = WeaponDefinition;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<WeaponDefinition, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: WeaponFile = ron::de::from_bytes(&bytes)?;
+
+        let image = load_context
+            .loader()
+            .with_settings(|settings: &mut ImageLoaderSettings| {
+                // Use `nearest` image sampling to preserve pixel art style.
+                settings.sampler = ImageSampler::nearest();
+            })
+            .load(file.sprite.path);
+        let atlas = file.sprite.frame_size.map(|frame_size| {
+            let frames = file.sprite.frames.max(1);
+            let layout = TextureAtlasLayout::from_grid(frame_size, frames, 1, None, None);
+            let indices = AnimationIndices {
+                first: 0,
+                last: frames as usize - 1,
+            };
+            (
+                load_context.add_labeled_asset("atlas".into(), layout),
+                indices,
+            )
+        });
+
+        Ok(WeaponDefinition {
+            name: file.name,
+            fire_rate: file.fire_rate,
+            spread: file.spread.to_radians(),
+            projectile_count: file.projectile_count.max(1),
+            projectile_speed: file.projectile_speed,
+            lifetime: file.lifetime,
+            damage: file.damage,
+            ammo: file.ammo,
+            ammo_price: file.ammo_price,
+            sprite: ProjectileSprite {
+                image,
+                atlas,
+                scale: file.sprite.scale,
+                collider_radius: file.sprite.collider_radius,
+            },
+            sound: file.sound.map(|path| load_context.load(path)),
+            homing: file.homing,
+            blast_radius: file.blast_radius,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["weapon.ron"]
+    }
+}
+
+/// A mounting point for a weapon on a ship.
+#[derive(Debug, Clone)]
+pub struct Hardpoint {
+    pub weapon: Handle<WeaponDefinition>,
+    /// Where projectiles spawn relative to the ship's center, before rotation.
+    pub offset: Vec2,
+}
+
+/// A weapon mounted on a ship, along with the ammo it has left.
+#[derive(Debug, Clone)]
+pub struct WeaponSlot {
+    pub definition: Handle<WeaponDefinition>,
+    pub ammo: Option<u32>,
+    pub offset: Vec2,
+}
+
+impl WeaponSlot {
+    pub fn has_ammo(&self) -> bool {
+        self.ammo.is_none_or(|ammo| ammo > 0)
+    }
+}
+
+/// The weapons a ship carries, one of which is selected and fired at a time.
 #[derive(Component, Debug)]
 pub struct Weapon {
     pub fire_rate_timer: Timer,
+    pub loadout: Vec<WeaponSlot>,
+    pub selected: usize,
+    /// Multiplies the damage of every projectile fired.
+    pub damage_multiplier: f32,
+    /// Multiplies the shots per second of every weapon in the loadout.
+    pub fire_rate_multiplier: f32,
 }
 impl Weapon {
-    pub fn new() -> Self {
+    /// A fully stocked slot for each hardpoint with a loaded weapon.
+    pub fn new(hardpoints: &[Hardpoint], definitions: &Assets<WeaponDefinition>) -> Self {
+        let loadout = hardpoints
+            .iter()
+            .filter_map(|hardpoint| {
+                let definition = definitions.get(&hardpoint.weapon)?;
+                Some(WeaponSlot {
+                    definition: hardpoint.weapon.clone(),
+                    ammo: definition.ammo,
+                    offset: hardpoint.offset,
+                })
+            })
+            .collect();
+
         Self {
-            fire_rate_timer: Timer::from_seconds(WEAPON_FIRE_RATE, TimerMode::Once),
+            fire_rate_timer: Timer::from_seconds(0.0, TimerMode::Once),
+            loadout,
+            selected: 0,
+            damage_multiplier: 1.0,
+            fire_rate_multiplier: 1.0,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&WeaponSlot> {
+        self.loadout.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.loadout.is_empty() {
+            self.selected = (self.selected + 1) % self.loadout.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.loadout.is_empty() {
+            self.selected = (self.selected + self.loadout.len() - 1) % self.loadout.len();
         }
     }
 }
@@ -28,19 +250,33 @@ impl Weapon {
 #[derive(Component, Debug)]
 pub struct Projectile {
     pub despawn_timer: Timer,
+    /// The entity that fired the projectile.
+    pub shooter: Entity,
+    /// Health removed from whatever the projectile hits.
+    pub damage: f32,
+    /// Also damages everything within this distance of the impact.
+    pub blast_radius: Option<f32>,
+}
+
+/// Steers a projectile towards the nearest damageable entity.
+#[derive(Component, Debug)]
+struct Homing {
+    /// Radians per second.
+    turn_rate: f32,
 }
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<WeaponDefinition>();
+    app.init_asset_loader::<WeaponDefinitionLoader>();
+
     app.add_message::<FireWeapon>().add_systems(
         Update,
         (
             (tick_weapon_cooldown, tick_projectile_timers)
                 .chain()
                 .in_set(AppSystems::TickTimers),
-            fire_weapon
-                .run_if(resource_exists::<ShipAssets>)
-                .in_set(AppSystems::RecordInput),
-            despawn_projectile.in_set(AppSystems::Update),
+            fire_weapon.in_set(AppSystems::RecordInput),
+            (steer_homing_projectiles, despawn_projectile).in_set(AppSystems::Update),
         )
             .in_set(PausableSystems),
     );
@@ -62,49 +298,130 @@ fn tick_projectile_timers(mut query: Query<&mut Projectile, With<Projectile>>, t
 
 fn fire_weapon(
     mut commands: Commands,
-    mut weapons: Query<(&Transform, &mut Weapon)>,
-    ship_assets: Res<ShipAssets>,
+    mut weapons: Query<(&Transform, &mut Weapon, Option<&CollisionLayers>)>,
+    definitions: Res<Assets<WeaponDefinition>>,
     mut weapon_fired: MessageReader<FireWeapon>,
 ) {
     for event in weapon_fired.read() {
         let trigger_entity = event.entity;
 
         // find weapon on trigger entity
-        let Ok((transform, mut weapon)) = weapons.get_mut(trigger_entity) else {
+        let Ok((transform, mut weapon, shooter_layers)) = weapons.get_mut(trigger_entity) else {
             return error!("failed to get entity to weapon to fire.");
         };
 
-        // check if weapon timer is finished
-        if weapon.fire_rate_timer.is_finished() {
-            // reset timer
-            weapon.fire_rate_timer = Timer::from_seconds(WEAPON_FIRE_RATE, TimerMode::Once);
+        // check if weapon timer is finished and there's something left to fire
+        if !weapon.fire_rate_timer.is_finished() {
+            continue;
+        }
+        let selected = weapon.selected;
+        let Some(slot) = weapon.loadout.get_mut(selected) else {
+            continue;
+        };
+        if !slot.has_ammo() {
+            continue;
+        }
+        let Some(definition) = definitions.get(&slot.definition) else {
+            continue;
+        };
+        if let Some(ammo) = &mut slot.ammo {
+            *ammo -= 1;
+        }
+        let offset = slot.offset;
+
+        // reset timer
+        let cooldown = definition.fire_rate / weapon.fire_rate_multiplier;
+        weapon.fire_rate_timer = Timer::from_seconds(cooldown, TimerMode::Once);
 
-            // fire projectile
-            // calculate where to spawn the projectile (in front of player)
-            let transform_vec: Vec3 =
-                transform.translation + transform.up() * PROJECTILE_FORWARD_SPAWN_SCALAR;
-            let linear_velocity: Vec3 = transform.up() * PROJECTILE_SPEED;
+        if let Some(sound) = &definition.sound {
+            commands.spawn(sound_effect(sound.clone()));
+        }
+
+        // fire projectiles, fanned out evenly across the spread
+        // calculate where to spawn the projectile (at the hardpoint)
+        let transform_vec: Vec3 = transform.translation + transform.rotation * offset.extend(0.0);
+        let count = definition.projectile_count;
+        for i in 0..count {
+            let offset = if count > 1 {
+                definition.spread * (i as f32 / (count - 1) as f32 - 0.5)
+            } else {
+                0.0
+            };
+            let rotation = transform.rotation * Quat::from_rotation_z(offset);
+            let linear_velocity: Vec3 = rotation * Vec3::Y * definition.projectile_speed;
+            let collider = Collider::circle(definition.sprite.collider_radius);
 
-            commands.spawn((
+            let mut projectile = commands.spawn((
                 DespawnOnExit(Screen::Gameplay),
                 RigidBody::Dynamic,
                 LinearVelocity(linear_velocity.xy()),
-                Collider::circle(100.0),
-                MassPropertiesBundle::from_shape(&Collider::circle(100.0), 1.0),
+                MassPropertiesBundle::from_shape(&collider, 1.0),
+                collider,
                 Sensor,
-                Sprite::from_image(ship_assets.projectile.clone()),
-                Transform::from_translation(transform_vec).with_scale(Vec3::splat(0.03)),
+                CollisionEventsEnabled,
+                GameLayer::projectile_layers(shooter_layers),
+                Transform::from_translation(transform_vec)
+                    .with_rotation(rotation)
+                    .with_scale(Vec3::splat(definition.sprite.scale)),
                 Projectile {
-                    despawn_timer: Timer::from_seconds(
-                        PROJECTILE_DESPAWN_TIME_SECONDS,
-                        TimerMode::Once,
-                    ),
+                    despawn_timer: Timer::from_seconds(definition.lifetime, TimerMode::Once),
+                    shooter: trigger_entity,
+                    damage: definition.damage * weapon.damage_multiplier,
+                    blast_radius: definition.blast_radius,
                 },
             ));
+
+            match &definition.sprite.atlas {
+                Some((layout, indices)) => {
+                    projectile.insert((
+                        Sprite::from_atlas_image(
+                            definition.sprite.image.clone(),
+                            TextureAtlas::from(layout.clone()),
+                        ),
+                        looping_animation(*indices, PROJECTILE_ANIMATION_FPS),
+                    ));
+                }
+                None => {
+                    projectile.insert(Sprite::from_image(definition.sprite.image.clone()));
+                }
+            }
+
+            if let Some(turn_rate) = definition.homing {
+                projectile.insert(Homing { turn_rate });
+            }
         }
     }
 }
 
+/// Turn homing projectiles towards the closest thing that can be damaged, other than their shooter.
+fn steer_homing_projectiles(
+    time: Res<Time>,
+    mut projectiles: Query<(&Projectile, &Homing, &mut LinearVelocity, &mut Transform)>,
+    targets: Query<(Entity, &GlobalTransform), (With<Health>, Without<Projectile>)>,
+) {
+    for (projectile, homing, mut velocity, mut transform) in &mut projectiles {
+        let position = transform.translation.xy();
+        let Some(target) = targets
+            .iter()
+            .filter(|(entity, _)| *entity != projectile.shooter)
+            .map(|(_, target)| target.translation().xy())
+            .filter(|target| target.distance(position) <= HOMING_RANGE)
+            .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)))
+        else {
+            continue;
+        };
+
+        let max_turn = homing.turn_rate * time.delta_secs();
+        let angle = velocity
+            .0
+            .angle_to(target - position)
+            .clamp(-max_turn, max_turn);
+        velocity.0 = Vec2::from_angle(angle).rotate(velocity.0);
+        // the sprites point up rather than along +x
+        transform.rotation = Quat::from_rotation_z(velocity.0.to_angle() - FRAC_PI_2);
+    }
+}
+
 /// Handle despawning projectiles
 fn despawn_projectile(
     mut commands: Commands,
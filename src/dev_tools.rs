@@ -1,6 +1,9 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
-use crate::screens::Screen;
+use crate::{
+    input::{Action, ActionInput},
+    screens::Screen,
+};
 use bevy::{
     dev_tools::{
         fps_overlay::{FpsOverlayConfig, FpsOverlayPlugin, FrameTimeGraphConfig},
@@ -35,12 +38,12 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, toggle_debug_ui);
 }
 
-fn toggle_debug_ui(input: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<FpsOverlayConfig>) {
-    if input.just_released(KeyCode::F11) {
+fn toggle_debug_ui(input: ActionInput, mut overlay: ResMut<FpsOverlayConfig>) {
+    if input.just_released(Action::ToggleFrameTimeGraph) {
         overlay.frame_time_graph_config.enabled = !overlay.frame_time_graph_config.enabled;
     }
 
-    if input.just_released(KeyCode::F12) {
+    if input.just_released(Action::ToggleFps) {
         overlay.enabled = !overlay.enabled;
     }
 }
@@ -0,0 +1,405 @@
+//! Logical input actions and the bindings that trigger them.
+//!
+//! Gameplay systems should ask [`ActionInput`] about an [`Action`] rather than
+//! reading raw key codes, so players can rebind their controls. Analog sticks
+//! aren't bindable and are read directly through [`ActionInput`].
+
+use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<InputMap>();
+    app.init_resource::<InputMap>();
+}
+
+/// Everything the player can do with their controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum Action {
+    /// Thrust forward, or up when using twin-stick controls.
+    Thrust,
+    /// Thrust backwards, or down when using twin-stick controls.
+    Reverse,
+    /// Turn left, or thrust left when using twin-stick controls.
+    RotateLeft,
+    /// Turn right, or thrust right when using twin-stick controls.
+    RotateRight,
+    Fire,
+    /// Aim at the cursor when using cursor-follow controls.
+    Aim,
+    /// Switch to the next weapon in the ship's loadout. The mouse wheel also switches weapons.
+    NextWeapon,
+    PreviousWeapon,
+    Pause,
+    ToggleFps,
+    ToggleFrameTimeGraph,
+}
+
+impl Action {
+    /// Every action, in the order they are listed on the controls menu.
+    pub const ALL: [Self; 11] = [
+        Self::Thrust,
+        Self::Reverse,
+        Self::RotateLeft,
+        Self::RotateRight,
+        Self::Fire,
+        Self::Aim,
+        Self::NextWeapon,
+        Self::PreviousWeapon,
+        Self::Pause,
+        Self::ToggleFps,
+        Self::ToggleFrameTimeGraph,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Thrust => "Thrust",
+            Self::Reverse => "Reverse",
+            Self::RotateLeft => "Rotate Left",
+            Self::RotateRight => "Rotate Right",
+            Self::Fire => "Fire",
+            Self::Aim => "Aim",
+            Self::NextWeapon => "Next Weapon",
+            Self::PreviousWeapon => "Previous Weapon",
+            Self::Pause => "Pause",
+            Self::ToggleFps => "Toggle FPS",
+            Self::ToggleFrameTimeGraph => "Toggle Frame Graph",
+        }
+    }
+}
+
+/// Stick deflection below this is treated as no input.
+const STICK_DEADZONE: f32 = 0.2;
+
+/// A physical input that can trigger an [`Action`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+impl Binding {
+    /// A short, human-readable name for the binding.
+    pub fn label(self) -> String {
+        match self {
+            Self::Key(key) => {
+                let name = format!("{key:?}");
+                // `KeyW` and `Digit1` read better as `W` and `1`
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .map(str::to_string)
+                    .unwrap_or(name)
+            }
+            Self::Mouse(button) => format!("Mouse {button:?}"),
+            Self::Gamepad(button) => format!("Pad {button:?}"),
+        }
+    }
+}
+
+/// Maps each [`Action`] to the [`Binding`]s that trigger it.
+///
+/// This is a copy of [`Settings::input_map`](crate::settings::Settings::input_map),
+/// which is where bindings should be changed.
+#[derive(Resource, Debug, Clone, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct InputMap {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use Binding::*;
+
+        Self {
+            bindings: HashMap::from_iter([
+                (
+                    Action::Thrust,
+                    vec![
+                        Key(KeyCode::KeyW),
+                        Key(KeyCode::ArrowUp),
+                        Gamepad(GamepadButton::DPadUp),
+                    ],
+                ),
+                (
+                    Action::Reverse,
+                    vec![
+                        Key(KeyCode::KeyS),
+                        Key(KeyCode::ArrowDown),
+                        Gamepad(GamepadButton::DPadDown),
+                    ],
+                ),
+                (
+                    Action::RotateLeft,
+                    vec![
+                        Key(KeyCode::KeyA),
+                        Key(KeyCode::ArrowLeft),
+                        Gamepad(GamepadButton::DPadLeft),
+                    ],
+                ),
+                (
+                    Action::RotateRight,
+                    vec![
+                        Key(KeyCode::KeyD),
+                        Key(KeyCode::ArrowRight),
+                        Gamepad(GamepadButton::DPadRight),
+                    ],
+                ),
+                (
+                    Action::Fire,
+                    vec![
+                        Mouse(MouseButton::Left),
+                        Key(KeyCode::Space),
+                        Gamepad(GamepadButton::RightTrigger2),
+                    ],
+                ),
+                (
+                    Action::Aim,
+                    vec![
+                        Mouse(MouseButton::Right),
+                        Gamepad(GamepadButton::LeftTrigger2),
+                    ],
+                ),
+                (
+                    Action::NextWeapon,
+                    vec![Key(KeyCode::KeyE), Gamepad(GamepadButton::RightTrigger)],
+                ),
+                (
+                    Action::PreviousWeapon,
+                    vec![Key(KeyCode::KeyQ), Gamepad(GamepadButton::LeftTrigger)],
+                ),
+                (
+                    Action::Pause,
+                    vec![Key(KeyCode::KeyP), Gamepad(GamepadButton::Start)],
+                ),
+                (Action::ToggleFps, vec![Key(KeyCode::F12)]),
+                (Action::ToggleFrameTimeGraph, vec![Key(KeyCode::F11)]),
+            ]),
+        }
+    }
+}
+
+impl InputMap {
+    /// Keys that are reserved for menu navigation and can't be bound to an action.
+    pub const RESERVED_KEYS: [KeyCode; 1] = [KeyCode::Escape];
+
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Give actions that aren't in the map yet, like ones added since the map was saved, their
+    /// default bindings. Defaults that are already bound to another action are skipped.
+    pub fn add_missing_actions(&mut self) {
+        let defaults = Self::default();
+        for action in Action::ALL {
+            if self.bindings.contains_key(&action) {
+                continue;
+            }
+
+            let bindings = defaults
+                .bindings(action)
+                .iter()
+                .copied()
+                .filter(|binding| self.conflict(action, *binding).is_none())
+                .collect();
+            self.bindings.insert(action, bindings);
+        }
+    }
+
+    /// The action other than `action` that `binding` is bound to, if any.
+    pub fn conflict(&self, action: Action, binding: Binding) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|other| *other != action && self.bindings(*other).contains(&binding))
+    }
+
+    /// Bind `binding` to `action` in the given slot, replacing whatever was there.
+    ///
+    /// If `action` already has `binding` in another slot, the two slots are swapped. If another
+    /// action was already bound to `binding`, the two actions swap bindings so that no input ever
+    /// triggers more than one action. Returns the action that was swapped with.
+    pub fn rebind(&mut self, action: Action, slot: usize, binding: Binding) -> Option<Action> {
+        let conflict = self.conflict(action, binding);
+
+        let bindings = self.bindings.entry(action).or_default();
+        if let Some(existing) = bindings.iter().position(|bound| *bound == binding) {
+            // already bound to this action in another slot, so swap the two slots. A new slot
+            // would only duplicate the binding, so leave it where it is in that case.
+            if slot < bindings.len() {
+                bindings.swap(existing, slot);
+            }
+            return None;
+        }
+        let replaced = if slot < bindings.len() {
+            Some(std::mem::replace(&mut bindings[slot], binding))
+        } else {
+            bindings.push(binding);
+            None
+        };
+
+        if let Some(other) = conflict {
+            let other_bindings = self.bindings.entry(other).or_default();
+            match (
+                other_bindings.iter().position(|bound| *bound == binding),
+                replaced,
+            ) {
+                (Some(index), Some(replaced)) => other_bindings[index] = replaced,
+                (Some(index), None) => {
+                    other_bindings.remove(index);
+                }
+                (None, _) => {}
+            }
+        }
+
+        conflict
+    }
+}
+
+/// Reads the state of [`Action`]s through the current [`InputMap`].
+#[derive(SystemParam)]
+pub struct ActionInput<'w, 's> {
+    input_map: Res<'w, InputMap>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl ActionInput<'_, '_> {
+    /// Whether any binding of `action` is held down.
+    pub fn pressed(&self, action: Action) -> bool {
+        self.input_map
+            .bindings(action)
+            .iter()
+            .any(|binding| match *binding {
+                Binding::Key(key) => self.keys.pressed(key),
+                Binding::Mouse(button) => self.mouse.pressed(button),
+                Binding::Gamepad(button) => self.gamepads.iter().any(|pad| pad.pressed(button)),
+            })
+    }
+
+    /// Whether any binding of `action` was pressed this frame.
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.input_map
+            .bindings(action)
+            .iter()
+            .any(|binding| match *binding {
+                Binding::Key(key) => self.keys.just_pressed(key),
+                Binding::Mouse(button) => self.mouse.just_pressed(button),
+                Binding::Gamepad(button) => {
+                    self.gamepads.iter().any(|pad| pad.just_pressed(button))
+                }
+            })
+    }
+
+    /// Whether any binding of `action` was released this frame.
+    pub fn just_released(&self, action: Action) -> bool {
+        self.input_map
+            .bindings(action)
+            .iter()
+            .any(|binding| match *binding {
+                Binding::Key(key) => self.keys.just_released(key),
+                Binding::Mouse(button) => self.mouse.just_released(button),
+                Binding::Gamepad(button) => {
+                    self.gamepads.iter().any(|pad| pad.just_released(button))
+                }
+            })
+    }
+
+    /// Left stick position of the first gamepad that is using it, or zero inside the deadzone.
+    pub fn left_stick(&self) -> Vec2 {
+        self.stick(Gamepad::left_stick)
+    }
+
+    /// Right stick position of the first gamepad that is using it, or zero inside the deadzone.
+    pub fn right_stick(&self) -> Vec2 {
+        self.stick(Gamepad::right_stick)
+    }
+
+    fn stick(&self, read: impl Fn(&Gamepad) -> Vec2) -> Vec2 {
+        self.gamepads
+            .iter()
+            .map(read)
+            .find(|stick| stick.length() > STICK_DEADZONE)
+            .unwrap_or(Vec2::ZERO)
+    }
+}
+
+/// Run condition that is active on the frame `action` is pressed.
+pub fn action_just_pressed(action: Action) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_pressed(action)
+}
+
+/// Run condition that is active on the frame `action` is released.
+pub fn action_just_released(action: Action) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_released(action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: Binding = Binding::Key(KeyCode::KeyA);
+    const B: Binding = Binding::Key(KeyCode::KeyB);
+    const C: Binding = Binding::Key(KeyCode::KeyC);
+    const D: Binding = Binding::Key(KeyCode::KeyD);
+
+    fn input_map(bindings: impl IntoIterator<Item = (Action, Vec<Binding>)>) -> InputMap {
+        InputMap {
+            bindings: HashMap::from_iter(bindings),
+        }
+    }
+
+    #[test]
+    fn rebind_replaces_the_slot() {
+        let mut map = input_map([(Action::Fire, vec![A, B])]);
+        assert_eq!(map.rebind(Action::Fire, 1, C), None);
+        assert_eq!(map.bindings(Action::Fire), [A, C]);
+    }
+
+    #[test]
+    fn rebind_past_the_last_slot_adds_a_binding() {
+        let mut map = input_map([(Action::Fire, vec![A])]);
+        assert_eq!(map.rebind(Action::Fire, 1, B), None);
+        assert_eq!(map.bindings(Action::Fire), [A, B]);
+    }
+
+    #[test]
+    fn rebind_swaps_slots_of_the_same_action() {
+        let mut map = input_map([(Action::Fire, vec![A, B])]);
+        assert_eq!(map.rebind(Action::Fire, 1, A), None);
+        assert_eq!(map.bindings(Action::Fire), [B, A]);
+
+        let mut map = input_map([(Action::Fire, vec![A, B, C])]);
+        assert_eq!(map.rebind(Action::Fire, 2, A), None);
+        assert_eq!(map.bindings(Action::Fire), [C, B, A]);
+    }
+
+    #[test]
+    fn rebind_to_the_same_slot_changes_nothing() {
+        let mut map = input_map([(Action::Fire, vec![A, B])]);
+        assert_eq!(map.rebind(Action::Fire, 0, A), None);
+        assert_eq!(map.bindings(Action::Fire), [A, B]);
+    }
+
+    #[test]
+    fn rebind_an_existing_binding_to_a_new_slot_keeps_one_copy() {
+        let mut map = input_map([(Action::Fire, vec![A, B])]);
+        assert_eq!(map.rebind(Action::Fire, 2, A), None);
+        assert_eq!(map.bindings(Action::Fire), [A, B]);
+    }
+
+    #[test]
+    fn rebind_swaps_bindings_with_a_conflicting_action() {
+        let mut map = input_map([(Action::Fire, vec![A, B]), (Action::Aim, vec![C, D])]);
+        assert_eq!(map.rebind(Action::Fire, 0, D), Some(Action::Aim));
+        assert_eq!(map.bindings(Action::Fire), [D, B]);
+        assert_eq!(map.bindings(Action::Aim), [C, A]);
+    }
+
+    #[test]
+    fn rebind_takes_a_conflicting_binding_into_a_new_slot() {
+        let mut map = input_map([(Action::Fire, vec![A]), (Action::Aim, vec![C, D])]);
+        assert_eq!(map.rebind(Action::Fire, 1, C), Some(Action::Aim));
+        assert_eq!(map.bindings(Action::Fire), [A, C]);
+        assert_eq!(map.bindings(Action::Aim), [D]);
+    }
+}
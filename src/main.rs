@@ -9,8 +9,11 @@ mod camera;
 mod demo;
 #[cfg(feature = "dev")]
 mod dev_tools;
+mod input;
 mod menus;
 mod screens;
+mod settings;
+mod storage;
 mod theme;
 
 use avian2d::prelude::*;
@@ -49,17 +52,22 @@ impl Plugin for AppPlugin {
         app.add_plugins((
             asset_tracking::plugin,
             audio::plugin,
+            camera::plugin,
             demo::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
+            input::plugin,
             menus::plugin,
             screens::plugin,
+            settings::plugin,
             theme::plugin,
         ));
 
         // Add physics plugins and specify a units-per-meter scaling factor, 1 meter = 64 pixels.
         // The unit allows the engine to tune its parameters for the scale of the world, improving stability.
         app.add_plugins(PhysicsPlugins::default().with_length_unit(64.0));
+        // There is no "down" in space.
+        app.insert_resource(Gravity(Vec2::ZERO));
 
         // Order new `AppSystems` variants by adding them here:
         app.configure_sets(
@@ -0,0 +1,222 @@
+//! The controls menu, where players can rebind their input actions.
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    AppSystems,
+    input::{Action, Binding, InputMap},
+    menus::Menu,
+    settings::Settings,
+    theme::{navigation::UiFocusLocked, prelude::*},
+};
+
+/// How many bindings can be set for each action from the menu.
+const BINDING_SLOTS: usize = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Menu::Controls),
+        (init_rebind_state, spawn_controls_menu),
+    );
+    app.add_systems(OnExit(Menu::Controls), remove_rebind_state);
+
+    app.register_type::<ControlsLabel>();
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(not(is_awaiting_binding).and(input_just_pressed(KeyCode::Escape))),
+            capture_binding.run_if(is_awaiting_binding),
+            lock_ui_focus,
+            update_controls_labels
+                .run_if(resource_changed::<Settings>.or(resource_changed::<RebindState>)),
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Menu::Controls)),
+    );
+}
+
+/// Progress of rebinding an action from the controls menu.
+#[derive(Resource, Debug, Default)]
+struct RebindState {
+    /// The action and binding slot waiting for the player to press something.
+    awaiting: Option<(Action, usize)>,
+    /// Feedback about the last rebind, such as a conflict that was resolved.
+    message: String,
+}
+
+fn init_rebind_state(mut commands: Commands) {
+    commands.init_resource::<RebindState>();
+}
+
+fn remove_rebind_state(mut commands: Commands, mut focus_locked: ResMut<UiFocusLocked>) {
+    commands.remove_resource::<RebindState>();
+    focus_locked.0 = false;
+}
+
+fn is_awaiting_binding(rebind_state: Option<Res<RebindState>>) -> bool {
+    rebind_state.is_some_and(|state| state.awaiting.is_some())
+}
+
+/// Keep menu navigation from reacting to the key being bound.
+fn lock_ui_focus(rebind_state: Res<RebindState>, mut focus_locked: ResMut<UiFocusLocked>) {
+    focus_locked.set_if_neq(UiFocusLocked(rebind_state.awaiting.is_some()));
+}
+
+fn spawn_controls_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Controls Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Controls),
+        children![
+            widget::header("Controls"),
+            bindings_grid(),
+            (widget::label(""), ControlsLabel::Message),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn bindings_grid() -> impl Bundle {
+    (
+        Name::new("Bindings Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(6.0),
+            column_gap: Px(20.0),
+            grid_template_columns: vec![
+                RepeatedGridTrack::px(1, 260.0),
+                RepeatedGridTrack::px(BINDING_SLOTS as u16, 220.0),
+            ],
+            ..default()
+        },
+        Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+            for action in Action::ALL {
+                parent.spawn((
+                    widget::label(action.label()),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        align_self: AlignSelf::Center,
+                        ..default()
+                    },
+                ));
+                for slot in 0..BINDING_SLOTS {
+                    parent.spawn((
+                        widget::button_medium(
+                            "",
+                            move |_: On<Pointer<Click>>, mut rebind_state: ResMut<RebindState>| {
+                                rebind_state.awaiting = Some((action, slot));
+                                rebind_state.message = format!(
+                                    "Rebinding {}. Press Escape to cancel.",
+                                    action.label()
+                                );
+                            },
+                        ),
+                        ControlsLabel::Binding(action, slot),
+                    ));
+                }
+            }
+        })),
+    )
+}
+
+/// Text on the controls menu that changes as bindings do. Kept up to date in place, so rebinding
+/// doesn't rebuild the menu and move keyboard / gamepad focus.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+enum ControlsLabel {
+    /// A binding slot's button.
+    Binding(Action, usize),
+    /// Feedback about the last rebind.
+    Message,
+}
+
+fn update_controls_labels(
+    settings: Res<Settings>,
+    rebind_state: Res<RebindState>,
+    labels: Query<(Entity, &ControlsLabel)>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, label) in &labels {
+        let value = match *label {
+            ControlsLabel::Binding(action, slot)
+                if rebind_state.awaiting == Some((action, slot)) =>
+            {
+                "Press a key...".to_string()
+            }
+            ControlsLabel::Binding(action, slot) => settings
+                .input_map
+                .bindings(action)
+                .get(slot)
+                .map_or("-".to_string(), |binding| binding.label()),
+            ControlsLabel::Message => rebind_state.message.clone(),
+        };
+
+        // a button's text is further down its hierarchy
+        for entity in std::iter::once(entity).chain(children.iter_descendants(entity)) {
+            if let Ok(mut text) = texts.get_mut(entity) {
+                text.0.clone_from(&value);
+            }
+        }
+    }
+}
+
+/// Bind the next key, mouse or gamepad button the player presses to the action being rebound.
+fn capture_binding(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut settings: ResMut<Settings>,
+    mut rebind_state: ResMut<RebindState>,
+) {
+    let Some((action, slot)) = rebind_state.awaiting else {
+        return;
+    };
+    // don't capture the button press that started rebinding
+    if rebind_state.is_changed() {
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::Escape) {
+        rebind_state.awaiting = None;
+        rebind_state.message = "Rebinding cancelled.".to_string();
+        return;
+    }
+
+    let binding = if let Some(key) = keys
+        .get_just_pressed()
+        .find(|key| !InputMap::RESERVED_KEYS.contains(key))
+    {
+        Binding::Key(*key)
+    } else if let Some(button) = mouse.get_just_pressed().next() {
+        Binding::Mouse(*button)
+    } else if let Some(button) = gamepads
+        .iter()
+        .find_map(|gamepad| gamepad.get_just_pressed().next())
+    {
+        Binding::Gamepad(*button)
+    } else {
+        return;
+    };
+
+    rebind_state.awaiting = None;
+    rebind_state.message = match settings.input_map.rebind(action, slot, binding) {
+        Some(other) => format!(
+            "{} was bound to {}, so the two swapped.",
+            binding.label(),
+            other.label()
+        ),
+        None => format!("{} bound to {}.", action.label(), binding.label()),
+    };
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
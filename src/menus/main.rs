@@ -1,38 +1,52 @@
 //! The main menu (seen on the title screen).
 
-use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    asset_tracking::ResourceHandles,
+    demo::save::{SaveLoader, SaveSlots},
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
 }
 
-fn spawn_main_menu(mut commands: Commands) {
-    commands.spawn((
-        widget::ui_root("Main Menu"),
-        GlobalZIndex(2),
-        DespawnOnExit(Menu::Main),
-        #[cfg(not(target_family = "wasm"))]
-        children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
-            widget::button("Exit", exit_app),
-        ],
-        #[cfg(target_family = "wasm")]
-        children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
-        ],
-    ));
-}
-
-fn enter_loading_or_gameplay_screen(
+fn spawn_main_menu(mut commands: Commands, save_slots: Res<SaveSlots>) {
+    let can_continue = save_slots
+        .active_save()
+        .is_some_and(|save| save.run.is_some());
+
+    commands
+        .spawn((
+            widget::ui_root("Main Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Main),
+        ))
+        .with_children(|parent| {
+            if can_continue {
+                parent.spawn(widget::button("Continue", continue_saved_run));
+            }
+            parent.spawn(widget::button("Play", enter_loading_or_ship_select_screen));
+            parent.spawn(widget::button("Load", open_load_menu));
+            parent.spawn(widget::button("Upgrades", open_upgrades_menu));
+            parent.spawn(widget::button("Settings", open_settings_menu));
+            parent.spawn(widget::button("Credits", open_credits_menu));
+            #[cfg(not(target_family = "wasm"))]
+            parent.spawn(widget::button("Exit", exit_app));
+        });
+}
+
+fn continue_saved_run(
     _: On<Pointer<Click>>,
+    mut save_loader: SaveLoader,
     resource_handles: Res<ResourceHandles>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
+    if !save_loader.load_active() {
+        return;
+    }
     if resource_handles.is_all_done() {
         next_screen.set(Screen::Gameplay);
     } else {
@@ -40,6 +54,26 @@ fn enter_loading_or_gameplay_screen(
     }
 }
 
+fn enter_loading_or_ship_select_screen(
+    _: On<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if resource_handles.is_all_done() {
+        next_screen.set(Screen::ShipSelect);
+    } else {
+        next_screen.set(Screen::Loading);
+    }
+}
+
+fn open_load_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Load);
+}
+
+fn open_upgrades_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Upgrades);
+}
+
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
@@ -1,9 +1,13 @@
 //! The game's menus and transitions between them.
 
+mod controls;
 mod credits;
 mod main;
 mod pause;
+mod save_slots;
 mod settings;
+mod station;
+mod upgrades;
 
 use bevy::prelude::*;
 
@@ -11,10 +15,14 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
+        controls::plugin,
         credits::plugin,
         main::plugin,
         settings::plugin,
         pause::plugin,
+        save_slots::plugin,
+        station::plugin,
+        upgrades::plugin,
     ));
 }
 
@@ -26,5 +34,10 @@ pub enum Menu {
     Main,
     Credits,
     Settings,
+    Controls,
     Pause,
+    Load,
+    Save,
+    Station,
+    Upgrades,
 }
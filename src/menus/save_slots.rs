@@ -0,0 +1,177 @@
+//! The save slot menus: one for loading a slot from the title screen, and one for saving the
+//! current run from the pause menu.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    demo::{
+        ore::Cargo,
+        player::Player,
+        save::{SLOT_COUNT, SaveData, SaveLoader, SaveSlots, SavedRun},
+        sector::SectorSeed,
+        ship::SelectedShip,
+        station::Credits,
+        stats::RunStats,
+        upgrades::Upgrades,
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Load), spawn_load_menu);
+    app.add_systems(OnEnter(Menu::Save), spawn_save_menu);
+    app.add_systems(
+        Update,
+        (
+            go_back_to_main_menu.run_if(in_state(Menu::Load)),
+            go_back_to_pause_menu.run_if(in_state(Menu::Save)),
+        )
+            .run_if(input_just_pressed(KeyCode::Escape)),
+    );
+
+    app.register_type::<SlotLabel>();
+    app.add_systems(
+        Update,
+        update_slot_labels.run_if(in_state(Menu::Load).or(in_state(Menu::Save))),
+    );
+}
+
+fn spawn_load_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            widget::ui_root("Load Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Load),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("Load game"));
+            for slot in 0..SLOT_COUNT {
+                parent.spawn(slot_row(
+                    slot,
+                    widget::button_medium(
+                        "Load",
+                        move |_: On<Pointer<Click>>,
+                              mut save_loader: SaveLoader,
+                              resource_handles: Res<ResourceHandles>,
+                              mut next_screen: ResMut<NextState<Screen>>,
+                              mut next_menu: ResMut<NextState<Menu>>| {
+                            if !save_loader.load(slot) {
+                                // nothing to resume, so stay on the title screen with the
+                                // slot's progress
+                                next_menu.set(Menu::Main);
+                            } else if resource_handles.is_all_done() {
+                                next_screen.set(Screen::Gameplay);
+                            } else {
+                                next_screen.set(Screen::Loading);
+                            }
+                        },
+                    ),
+                ));
+            }
+            parent.spawn(widget::button("Back", go_back_to_main_menu_on_click));
+        });
+}
+
+fn spawn_save_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            widget::ui_root("Save Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Save),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("Save game"));
+            for slot in 0..SLOT_COUNT {
+                parent.spawn(slot_row(
+                    slot,
+                    widget::button_medium(
+                        "Save",
+                        move |_: On<Pointer<Click>>,
+                              mut save_slots: ResMut<SaveSlots>,
+                              credits: Res<Credits>,
+                              upgrades: Res<Upgrades>,
+                              sector_seed: Res<SectorSeed>,
+                              selected_ship: Res<SelectedShip>,
+                              stats: Res<RunStats>,
+                              cargo: Single<&Cargo, With<Player>>| {
+                            let run = SavedRun::new(&sector_seed, &selected_ship, &cargo, &stats);
+                            let save = SaveData::new(&credits, &upgrades, Some(run));
+                            save_slots.save(slot, save);
+                        },
+                    ),
+                ));
+            }
+            parent.spawn(widget::button("Back", go_back_to_pause_menu_on_click));
+        });
+}
+
+/// A summary of what's in the slot, next to a button that loads or saves it.
+fn slot_row(slot: usize, button: impl Bundle) -> impl Bundle {
+    (
+        Name::new(format!("Slot {} Row", slot + 1)),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: Px(30.0),
+            ..default()
+        },
+        children![
+            (
+                widget::label(""),
+                Node {
+                    width: Px(560.0),
+                    ..default()
+                },
+                SlotLabel(slot),
+            ),
+            button,
+        ],
+    )
+}
+
+/// Shows what's saved in a slot.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct SlotLabel(usize);
+
+fn update_slot_labels(save_slots: Res<SaveSlots>, mut labels: Query<(&SlotLabel, &mut Text)>) {
+    for (label, mut text) in &mut labels {
+        let slot = label.0;
+        let active = if slot == save_slots.active {
+            " (active)"
+        } else {
+            ""
+        };
+        let summary = match save_slots.get(slot) {
+            None => "Empty".to_string(),
+            Some(save) => {
+                let mut summary =
+                    format!("Credits {}, {} upgrades", save.credits, save.upgrades.len());
+                if let Some(run) = &save.run {
+                    summary +=
+                        &format!(", sector {} at score {}", run.sector_seed, run.stats.score);
+                }
+                summary
+            }
+        };
+        text.0 = format!("Slot {}{active}: {summary}", slot + 1);
+    }
+}
+
+fn go_back_to_main_menu_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back_to_main_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back_to_pause_menu_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
+
+fn go_back_to_pause_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
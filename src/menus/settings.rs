@@ -2,9 +2,11 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    audio::AudioChannel, menus::Menu, screens::Screen, settings::Settings, theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -13,10 +15,10 @@ pub(super) fn plugin(app: &mut App) {
         go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
     );
 
-    app.register_type::<GlobalVolumeLabel>();
+    app.register_type::<SettingLabel>();
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        update_setting_labels.run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -28,6 +30,7 @@ fn spawn_settings_menu(mut commands: Commands) {
         children![
             widget::header("Settings"),
             settings_grid(),
+            widget::button("Controls", open_controls_menu),
             widget::button("Back", go_back_on_click),
         ],
     ));
@@ -44,37 +47,99 @@ fn settings_grid() -> impl Bundle {
             ..default()
         },
         children![
+            setting_name("Master Volume"),
+            setting_widget(
+                SettingLabel::Volume,
+                widget::button_small("-", lower_global_volume),
+                widget::button_small("+", raise_global_volume),
+            ),
+            setting_name(AudioChannel::Music.label()),
+            channel_volume_widget(AudioChannel::Music),
+            setting_name(AudioChannel::SoundEffects.label()),
+            channel_volume_widget(AudioChannel::SoundEffects),
+            setting_name(AudioChannel::Ui.label()),
+            channel_volume_widget(AudioChannel::Ui),
+            setting_name("Steering"),
+            setting_widget(
+                SettingLabel::ControlScheme,
+                widget::button_small("<", previous_control_scheme),
+                widget::button_small(">", next_control_scheme),
+            ),
+            setting_name("Display"),
+            setting_widget(
+                SettingLabel::DisplayMode,
+                widget::button_small("<", previous_display_mode),
+                widget::button_small(">", next_display_mode),
+            ),
+            setting_name("UI Scale"),
+            setting_widget(
+                SettingLabel::UiScale,
+                widget::button_small("-", lower_ui_scale),
+                widget::button_small("+", raise_ui_scale),
+            ),
+            setting_name("Reduce Flashing"),
+            toggle_widget(
+                SettingLabel::ReduceFlashing,
+                widget::button_medium("Toggle", toggle_reduce_flashing),
+            ),
+        ],
+    )
+}
+
+fn setting_name(text: &'static str) -> impl Bundle {
+    (
+        widget::label(text),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
+/// The current value of a setting between buttons that change it.
+fn setting_widget(label: SettingLabel, lower: impl Bundle, raise: impl Bundle) -> impl Bundle {
+    (
+        Name::new(format!("{label:?} Widget")),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            lower,
             (
-                widget::label("Master Volume"),
+                Name::new("Current Value"),
                 Node {
-                    justify_self: JustifySelf::End,
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
                     ..default()
-                }
+                },
+                children![(widget::label(""), label)],
             ),
-            global_volume_widget(),
+            raise,
         ],
     )
 }
 
-fn global_volume_widget() -> impl Bundle {
+/// The current state of an on / off setting, followed by a button that flips it.
+fn toggle_widget(label: SettingLabel, toggle: impl Bundle) -> impl Bundle {
     (
-        Name::new("Global Volume Widget"),
+        Name::new(format!("{label:?} Widget")),
         Node {
             justify_self: JustifySelf::Start,
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
             ..default()
         },
         children![
-            widget::button_small("-", lower_global_volume),
             (
-                Name::new("Current Volume"),
+                widget::label(""),
                 Node {
-                    padding: UiRect::horizontal(Px(10.0)),
-                    justify_content: JustifyContent::Center,
+                    width: Px(60.0),
                     ..default()
                 },
-                children![(widget::label(""), GlobalVolumeLabel)],
+                label,
             ),
-            widget::button_small("+", raise_global_volume),
+            toggle,
         ],
     )
 }
@@ -82,26 +147,126 @@ fn global_volume_widget() -> impl Bundle {
 const MIN_VOLUME: f32 = 0.0;
 const MAX_VOLUME: f32 = 3.0;
 
-fn lower_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() - 0.1).max(MIN_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+fn lower_global_volume(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.volume = (settings.volume - 0.1).max(MIN_VOLUME);
+}
+
+fn raise_global_volume(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.volume = (settings.volume + 0.1).min(MAX_VOLUME);
+}
+
+/// Volume buttons for a channel, followed by a button that mutes it.
+fn channel_volume_widget(channel: AudioChannel) -> impl Bundle {
+    (
+        Name::new(format!("{channel:?} Volume Widget")),
+        Node {
+            justify_self: JustifySelf::Start,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            setting_widget(
+                SettingLabel::ChannelVolume(channel),
+                widget::button_small(
+                    "-",
+                    move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+                        let volume = settings.channels.get_mut(channel);
+                        volume.level = (volume.level - 0.1).max(MIN_VOLUME);
+                    },
+                ),
+                widget::button_small(
+                    "+",
+                    move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+                        let volume = settings.channels.get_mut(channel);
+                        volume.level = (volume.level + 0.1).min(MAX_VOLUME);
+                    },
+                ),
+            ),
+            widget::button_small(
+                "M",
+                move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+                    let volume = settings.channels.get_mut(channel);
+                    volume.muted = !volume.muted;
+                },
+            ),
+        ],
+    )
+}
+
+fn previous_control_scheme(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.control_scheme = settings.control_scheme.previous();
+}
+
+fn next_control_scheme(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.control_scheme = settings.control_scheme.next();
 }
 
-fn raise_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() + 0.1).min(MAX_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+fn previous_display_mode(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.display_mode = settings.display_mode.previous();
 }
 
-#[derive(Component, Reflect)]
+fn next_display_mode(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.display_mode = settings.display_mode.next();
+}
+
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 2.0;
+
+fn lower_ui_scale(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    let ui_scale = &mut settings.accessibility.ui_scale;
+    *ui_scale = (*ui_scale - 0.1).max(MIN_UI_SCALE);
+}
+
+fn raise_ui_scale(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    let ui_scale = &mut settings.accessibility.ui_scale;
+    *ui_scale = (*ui_scale + 0.1).min(MAX_UI_SCALE);
+}
+
+fn toggle_reduce_flashing(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    let reduce_flashing = &mut settings.accessibility.reduce_flashing;
+    *reduce_flashing = !*reduce_flashing;
+}
+
+/// Shows the current value of a setting.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
 #[reflect(Component)]
-struct GlobalVolumeLabel;
+enum SettingLabel {
+    Volume,
+    ChannelVolume(AudioChannel),
+    ControlScheme,
+    DisplayMode,
+    UiScale,
+    ReduceFlashing,
+}
 
-fn update_global_volume_label(
-    global_volume: Res<GlobalVolume>,
-    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
-) {
-    let percent = 100.0 * global_volume.volume.to_linear();
-    label.0 = format!("{percent:3.0}%");
+fn update_setting_labels(settings: Res<Settings>, mut labels: Query<(&SettingLabel, &mut Text)>) {
+    for (label, mut text) in &mut labels {
+        text.0 = match *label {
+            SettingLabel::Volume => format!("{:3.0}%", 100.0 * settings.volume),
+            SettingLabel::ChannelVolume(channel) => {
+                let volume = settings.channels.get(channel);
+                if volume.muted {
+                    "Muted".to_string()
+                } else {
+                    format!("{:3.0}%", 100.0 * volume.level)
+                }
+            }
+            SettingLabel::ControlScheme => settings.control_scheme.label().to_string(),
+            SettingLabel::DisplayMode => settings.display_mode.label().to_string(),
+            SettingLabel::UiScale => format!("{:3.0}%", 100.0 * settings.accessibility.ui_scale),
+            SettingLabel::ReduceFlashing => {
+                if settings.accessibility.reduce_flashing {
+                    "On".to_string()
+                } else {
+                    "Off".to_string()
+                }
+            }
+        };
+    }
+}
+
+fn open_controls_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Controls);
 }
 
 fn go_back_on_click(
@@ -0,0 +1,184 @@
+//! The station menu, opened by docking at a station.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+
+use crate::{
+    demo::{
+        combat::{Health, Shield},
+        ore::Cargo,
+        player::Player,
+        station::{
+            Credits, Refit, cargo_value, repair_hull, repair_price, restock_ammo, restock_price,
+            sell_cargo,
+        },
+        weapon::{Weapon, WeaponDefinition},
+    },
+    menus::Menu,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Station), spawn_station_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Station).and(input_just_pressed(KeyCode::Escape))),
+    );
+
+    app.register_type::<StationLabel>();
+    app.add_systems(
+        Update,
+        update_station_labels.run_if(in_state(Menu::Station)),
+    );
+}
+
+fn spawn_station_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Station Menu"),
+        GlobalZIndex(2),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        DespawnOnExit(Menu::Station),
+        children![
+            widget::header("Station"),
+            (widget::label(""), StationLabel::Credits),
+            services_grid(),
+            widget::button("Undock", undock),
+        ],
+    ));
+}
+
+fn services_grid() -> impl Bundle {
+    (
+        Name::new("Services Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            align_items: AlignItems::Center,
+            grid_template_columns: vec![
+                GridTrack::px(360.0),
+                GridTrack::px(120.0),
+                GridTrack::auto(),
+            ],
+            ..default()
+        },
+        children![
+            service_name("Sell ore"),
+            (widget::label(""), StationLabel::CargoValue),
+            widget::button_medium("Sell", sell_ore),
+            service_name("Repair hull"),
+            (widget::label(""), StationLabel::RepairPrice),
+            widget::button_medium("Repair", repair),
+            service_name("Restock ammo"),
+            (widget::label(""), StationLabel::AmmoPrice),
+            widget::button_medium("Restock", restock),
+            refit_row(Refit::ALL[0]),
+            refit_row(Refit::ALL[1]),
+            refit_row(Refit::ALL[2]),
+        ],
+    )
+}
+
+fn service_name(text: &'static str) -> impl Bundle {
+    (
+        widget::label(text),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
+fn refit_row(refit: Refit) -> impl Bundle {
+    (
+        Name::new(format!("{refit:?} Refit")),
+        // lay the row out in the grid's columns
+        Node {
+            display: Display::Contents,
+            ..default()
+        },
+        children![
+            service_name(refit.label()),
+            (widget::label(""), StationLabel::Refit(refit)),
+            widget::button_medium(
+                "Fit",
+                move |_: On<Pointer<Click>>,
+                      mut credits: ResMut<Credits>,
+                      player: Single<
+                    (&mut Health, Option<&mut Shield>, &mut Cargo),
+                    With<Player>,
+                >| {
+                    let (mut health, shield, mut cargo) = player.into_inner();
+                    refit.buy(
+                        &mut credits,
+                        &mut health,
+                        shield.map(Mut::into_inner),
+                        &mut cargo,
+                    );
+                },
+            ),
+        ],
+    )
+}
+
+fn sell_ore(
+    _: On<Pointer<Click>>,
+    mut credits: ResMut<Credits>,
+    mut cargo: Single<&mut Cargo, With<Player>>,
+) {
+    sell_cargo(&mut cargo, &mut credits);
+}
+
+fn repair(
+    _: On<Pointer<Click>>,
+    mut credits: ResMut<Credits>,
+    player: Single<(&mut Health, Option<&mut Shield>), With<Player>>,
+) {
+    let (mut health, shield) = player.into_inner();
+    repair_hull(&mut health, shield.map(Mut::into_inner), &mut credits);
+}
+
+fn restock(
+    _: On<Pointer<Click>>,
+    mut credits: ResMut<Credits>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    mut weapon: Single<&mut Weapon, With<Player>>,
+) {
+    restock_ammo(&mut weapon, &definitions, &mut credits);
+}
+
+/// Shows the player's credits, or the price of a service.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+enum StationLabel {
+    Credits,
+    CargoValue,
+    RepairPrice,
+    AmmoPrice,
+    Refit(Refit),
+}
+
+fn update_station_labels(
+    credits: Res<Credits>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    player: Single<(&Health, &Cargo, &Weapon), With<Player>>,
+    mut labels: Query<(&StationLabel, &mut Text)>,
+) {
+    let (health, cargo, weapon) = *player;
+    for (label, mut text) in &mut labels {
+        text.0 = match *label {
+            StationLabel::Credits => format!("Credits {}", credits.0),
+            StationLabel::CargoValue => format!("+{}", cargo_value(cargo)),
+            StationLabel::RepairPrice => repair_price(health).to_string(),
+            StationLabel::AmmoPrice => restock_price(weapon, &definitions).to_string(),
+            StationLabel::Refit(refit) => refit.price().to_string(),
+        };
+    }
+}
+
+fn undock(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
@@ -0,0 +1,149 @@
+//! The upgrades menu, where credits are spent on permanent ship upgrades.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+
+use crate::{
+    demo::{
+        station::Credits,
+        upgrades::{Upgrade, UpgradeState, Upgrades},
+    },
+    menus::Menu,
+    theme::{
+        palette::{LABEL_TEXT, LOCKED_TEXT, PURCHASED_TEXT},
+        prelude::*,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Upgrades), spawn_upgrades_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Upgrades).and(input_just_pressed(KeyCode::Escape))),
+    );
+
+    app.register_type::<UpgradeLabel>();
+    app.add_systems(
+        Update,
+        update_upgrade_labels.run_if(in_state(Menu::Upgrades)),
+    );
+}
+
+fn spawn_upgrades_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            widget::ui_root("Upgrades Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Upgrades),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("Upgrades"));
+            parent.spawn((widget::label(""), UpgradeLabel::Credits));
+            parent
+                .spawn((
+                    Name::new("Upgrade Tree"),
+                    Node {
+                        column_gap: Px(30.0),
+                        align_items: AlignItems::Start,
+                        ..default()
+                    },
+                ))
+                .with_children(|parent| {
+                    for (name, upgrades) in Upgrade::BRANCHES {
+                        parent.spawn(branch(name)).with_children(|parent| {
+                            for upgrade in upgrades {
+                                parent.spawn(upgrade_node(*upgrade));
+                            }
+                        });
+                    }
+                });
+            parent.spawn(widget::button("Back", go_back_on_click));
+        });
+}
+
+/// A column of upgrade nodes under the branch's name.
+fn branch(name: &'static str) -> impl Bundle {
+    (
+        Name::new(format!("{name} Branch")),
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Px(12.0),
+            ..default()
+        },
+        children![widget::label(name)],
+    )
+}
+
+/// A button to buy the upgrade, with what it does and whether it can be bought.
+fn upgrade_node(upgrade: Upgrade) -> impl Bundle {
+    (
+        Name::new(format!("{upgrade:?} Node")),
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![
+            widget::button_medium(
+                upgrade.label(),
+                move |_: On<Pointer<Click>>,
+                      mut upgrades: ResMut<Upgrades>,
+                      mut credits: ResMut<Credits>| {
+                    upgrades.buy(upgrade, &mut credits);
+                },
+            ),
+            small_label(upgrade.effect().label()),
+            (small_label(""), UpgradeLabel::State(upgrade)),
+        ],
+    )
+}
+
+fn small_label(text: impl Into<String>) -> impl Bundle {
+    (
+        Name::new("Label"),
+        Text(text.into()),
+        TextFont::from_font_size(18.0),
+        TextColor(LABEL_TEXT),
+    )
+}
+
+/// Shows the player's credits, or whether an upgrade is locked, for sale or already bought.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+enum UpgradeLabel {
+    Credits,
+    State(Upgrade),
+}
+
+fn update_upgrade_labels(
+    credits: Res<Credits>,
+    upgrades: Res<Upgrades>,
+    mut labels: Query<(&UpgradeLabel, &mut Text, &mut TextColor)>,
+) {
+    for (label, mut text, mut color) in &mut labels {
+        match *label {
+            UpgradeLabel::Credits => text.0 = format!("Credits {}", credits.0),
+            UpgradeLabel::State(upgrade) => {
+                let state = upgrades.state(upgrade);
+                text.0 = match state {
+                    UpgradeState::Locked => "Locked".to_string(),
+                    UpgradeState::Unlocked => format!("{} credits", upgrade.price()),
+                    UpgradeState::Purchased => "Purchased".to_string(),
+                };
+                color.0 = match state {
+                    UpgradeState::Locked => LOCKED_TEXT,
+                    UpgradeState::Unlocked => LABEL_TEXT,
+                    UpgradeState::Purchased => PURCHASED_TEXT,
+                };
+            }
+        }
+    }
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
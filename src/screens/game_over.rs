@@ -0,0 +1,88 @@
+//! The screen shown once the player has run out of lives, with a summary of the run.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    demo::{sector::Sector, stats::RunStats},
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::GameOver), spawn_game_over_screen);
+}
+
+fn spawn_game_over_screen(mut commands: Commands, stats: Res<RunStats>, sector: Res<Sector>) {
+    commands.spawn((
+        widget::ui_root("Game Over Screen"),
+        DespawnOnExit(Screen::GameOver),
+        children![
+            widget::header("Game Over"),
+            run_summary(&stats, &sector),
+            widget::button("Retry sector", retry),
+            widget::button("Quit to title", quit_to_title),
+        ],
+    ));
+}
+
+fn run_summary(stats: &RunStats, sector: &Sector) -> impl Bundle {
+    let seconds = stats.time_survived.as_secs();
+    let accuracy = stats.accuracy().map_or("-".to_string(), |accuracy| {
+        format!("{:.0}%", 100.0 * accuracy)
+    });
+
+    (
+        Name::new("Run Summary"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(6.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 300.0),
+            ..default()
+        },
+        children![
+            summary_name("Score"),
+            summary_value(stats.score.to_string()),
+            summary_name("Ore Mined"),
+            summary_value(stats.ore_mined.to_string()),
+            summary_name("Asteroids Destroyed"),
+            summary_value(stats.asteroids_destroyed.to_string()),
+            summary_name("Enemies Destroyed"),
+            summary_value(stats.enemies_destroyed.to_string()),
+            summary_name("Accuracy"),
+            summary_value(accuracy),
+            summary_name("Time Survived"),
+            summary_value(format!("{}:{:02}", seconds / 60, seconds % 60)),
+            summary_name("Sector"),
+            summary_value(sector.seed.to_string()),
+        ],
+    )
+}
+
+fn summary_name(text: &'static str) -> impl Bundle {
+    (
+        widget::label(text),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
+fn summary_value(text: String) -> impl Bundle {
+    (
+        widget::label(text),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+    )
+}
+
+fn retry(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Gameplay);
+}
+
+fn quit_to_title(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
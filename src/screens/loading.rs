@@ -3,14 +3,21 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, screens::Screen, theme::prelude::*};
+use crate::{
+    asset_tracking::ResourceHandles, demo::save::resuming_run, screens::Screen, theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen);
 
     app.add_systems(
         Update,
-        enter_gameplay_screen.run_if(in_state(Screen::Loading).and(all_assets_loaded)),
+        (
+            enter_ship_select_screen.run_if(not(resuming_run)),
+            // a resumed run already knows its ship and sector
+            enter_gameplay_screen.run_if(resuming_run),
+        )
+            .run_if(in_state(Screen::Loading).and(all_assets_loaded)),
     );
 }
 
@@ -22,6 +29,10 @@ fn spawn_loading_screen(mut commands: Commands) {
     ));
 }
 
+fn enter_ship_select_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::ShipSelect);
+}
+
 fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Gameplay);
 }
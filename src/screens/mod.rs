@@ -1,7 +1,9 @@
 //! The game's main screen states and transitions between them.
 
+mod game_over;
 mod gameplay;
 mod loading;
+mod ship_select;
 mod splash;
 mod title;
 
@@ -11,8 +13,10 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
 
     app.add_plugins((
+        game_over::plugin,
         gameplay::plugin,
         loading::plugin,
+        ship_select::plugin,
         splash::plugin,
         title::plugin,
     ));
@@ -26,5 +30,7 @@ pub enum Screen {
     Splash,
     Title,
     Loading,
+    ShipSelect,
     Gameplay,
+    GameOver,
 }
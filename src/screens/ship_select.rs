@@ -0,0 +1,135 @@
+//! The screen where the player chooses which hull to fly before gameplay starts.
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    demo::{
+        sector::SectorSeed,
+        ship::{HullAssets, SelectedShip, ShipDefinition},
+        weapon::WeaponDefinition,
+    },
+    screens::Screen,
+    theme::prelude::*,
+};
+
+const CARD_WIDTH: f32 = 300.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::ShipSelect), spawn_ship_select_screen);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Screen::ShipSelect).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_ship_select_screen(
+    mut commands: Commands,
+    hull_assets: Res<HullAssets>,
+    sector_seed: Res<SectorSeed>,
+    ship_definitions: Res<Assets<ShipDefinition>>,
+    weapon_definitions: Res<Assets<WeaponDefinition>>,
+) {
+    let cards: Vec<_> = hull_assets
+        .hulls
+        .iter()
+        .enumerate()
+        .filter_map(|(index, handle)| {
+            let ship = ship_definitions.get(handle)?;
+            Some(ship_card(index, ship, &weapon_definitions))
+        })
+        .collect();
+
+    commands.spawn((
+        widget::ui_root("Ship Select Screen"),
+        DespawnOnExit(Screen::ShipSelect),
+        children![
+            widget::header("Choose your ship"),
+            widget::label(format!("Sector {}", sector_seed.seed)),
+            (
+                Name::new("Ship Cards"),
+                Node {
+                    column_gap: Px(20.0),
+                    ..default()
+                },
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    for card in cards {
+                        parent.spawn(card);
+                    }
+                })),
+            ),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn ship_card(
+    index: usize,
+    ship: &ShipDefinition,
+    weapon_definitions: &Assets<WeaponDefinition>,
+) -> impl Bundle {
+    let weapons = ship
+        .hardpoints
+        .iter()
+        .filter_map(|hardpoint| weapon_definitions.get(&hardpoint.weapon))
+        .map(|weapon| weapon.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let stats = [
+        format!("Hull {}", ship.hull),
+        format!("Shield {}", ship.shield),
+        format!("Top speed {}", ship.max_speed),
+        format!("Turn rate {:.0}°/s", ship.turn_rate.to_degrees()),
+        format!("Cargo {}", ship.cargo_capacity),
+        weapons,
+    ];
+
+    (
+        Name::new(format!("{} Card", ship.name)),
+        Node {
+            width: Px(CARD_WIDTH),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Px(8.0),
+            padding: UiRect::all(Px(16.0)),
+            ..default()
+        },
+        BackgroundColor(ui_palette::PANEL_BACKGROUND),
+        BorderRadius::all(Px(12.0)),
+        children![
+            widget::header(ship.name.clone()),
+            widget::label(ship.description.clone()),
+            (
+                Name::new("Stats"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    for stat in stats {
+                        parent.spawn(widget::label(stat));
+                    }
+                })),
+            ),
+            widget::button_medium(
+                "Fly",
+                move |_: On<Pointer<Click>>,
+                      mut selected_ship: ResMut<SelectedShip>,
+                      mut next_screen: ResMut<NextState<Screen>>| {
+                    selected_ship.0 = index;
+                    next_screen.set(Screen::Gameplay);
+                },
+            ),
+        ],
+    )
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
+
+fn go_back(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -0,0 +1,222 @@
+//! User settings that persist between launches.
+//!
+//! [`Settings`] is loaded while the app is being built, before the first screen is shown, and is
+//! the source of truth for everything it covers. Menus change [`Settings`], and the change is
+//! applied to the rest of the game (volume levels, input bindings, window mode, UI scale) and saved.
+//!
+//! Native builds save to `settings.ron` in the platform config directory, web builds save to
+//! the browser's local storage. See [`storage`](crate::storage).
+
+use bevy::{
+    audio::Volume,
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::ChannelVolumes,
+    demo::movement::ControlScheme,
+    input::InputMap,
+    storage::{self, Versioned},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Settings>();
+    app.insert_resource(Settings::load());
+
+    app.add_systems(Update, apply_settings.run_if(resource_changed::<Settings>));
+}
+
+/// Everything the player can configure from the settings menus.
+///
+/// Missing fields fall back to their defaults, so settings saved by an older version of the game
+/// still load.
+#[derive(Resource, Debug, Clone, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Settings {
+    /// The format version of the saved settings, see [`Versioned`].
+    version: u32,
+    /// Master volume, linear from 0 (muted) upwards.
+    pub volume: f32,
+    /// Music, sound effect and UI volume, on top of the master volume.
+    pub channels: ChannelVolumes,
+    pub display_mode: DisplayMode,
+    pub control_scheme: ControlScheme,
+    pub input_map: InputMap,
+    pub accessibility: AccessibilitySettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            volume: 1.0,
+            channels: ChannelVolumes::default(),
+            display_mode: DisplayMode::default(),
+            control_scheme: ControlScheme::default(),
+            input_map: InputMap::default(),
+            accessibility: AccessibilitySettings::default(),
+        }
+    }
+}
+
+/// How the game window is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    BorderlessFullscreen,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    const ALL: [Self; 3] = [Self::Windowed, Self::BorderlessFullscreen, Self::Fullscreen];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Windowed => "Windowed",
+            Self::BorderlessFullscreen => "Borderless",
+            Self::Fullscreen => "Fullscreen",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|mode| *mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|mode| *mode == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    fn window_mode(self) -> WindowMode {
+        match self {
+            Self::Windowed => WindowMode::Windowed,
+            Self::BorderlessFullscreen => {
+                WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+            }
+            Self::Fullscreen => {
+                WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    /// Scale factor for all UI.
+    pub ui_scale: f32,
+    /// Skip effects that flash the screen or sprites, like the hit flash.
+    pub reduce_flashing: bool,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            reduce_flashing: false,
+        }
+    }
+}
+
+impl Settings {
+    const FILE: &str = "settings";
+
+    /// Load the saved settings, or the defaults if there are none or they can't be read.
+    fn load() -> Self {
+        storage::load(Self::FILE).unwrap_or_default()
+    }
+}
+
+impl Versioned for Settings {
+    const VERSION: u32 = 1;
+
+    fn parse(_version: u32, contents: &str) -> Result<Self, String> {
+        let mut settings: Self = storage::from_ron(contents)?;
+        settings.input_map.add_missing_actions();
+        Ok(Self {
+            version: Self::VERSION,
+            ..settings
+        })
+    }
+}
+
+/// Push the settings out to the resources they control, then save them.
+fn apply_settings(
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut channel_volumes: ResMut<ChannelVolumes>,
+    mut input_map: ResMut<InputMap>,
+    mut control_scheme: ResMut<ControlScheme>,
+    mut ui_scale: ResMut<UiScale>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    global_volume.volume = Volume::Linear(settings.volume);
+    *channel_volumes = settings.channels;
+    *input_map = settings.input_map.clone();
+    *control_scheme = settings.control_scheme;
+    ui_scale.0 = settings.accessibility.ui_scale;
+
+    let window_mode = settings.display_mode.window_mode();
+    if window.mode != window_mode {
+        window.mode = window_mode;
+    }
+
+    // the settings were just loaded, so there's nothing new to save
+    if !settings.is_added() {
+        storage::save(Settings::FILE, &*settings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{Action, Binding};
+
+    use super::*;
+
+    #[test]
+    fn parse_fills_in_missing_fields() {
+        let settings = storage::parse::<Settings>("(volume: 0.5)").unwrap();
+        assert_eq!(settings.version, Settings::VERSION);
+        assert_eq!(settings.volume, 0.5);
+        assert_eq!(settings.display_mode, DisplayMode::Windowed);
+        assert!(!settings.accessibility.reduce_flashing);
+        assert_eq!(
+            settings.input_map.bindings(Action::Thrust),
+            InputMap::default().bindings(Action::Thrust)
+        );
+    }
+
+    #[test]
+    fn parse_adds_missing_actions_without_conflicts() {
+        let settings =
+            storage::parse::<Settings>("(input_map: (bindings: { Fire: [Key(KeyW)] }))").unwrap();
+        assert_eq!(
+            settings.input_map.bindings(Action::Fire),
+            [Binding::Key(KeyCode::KeyW)]
+        );
+        // W is the default for thrust, but fire already has it
+        assert_eq!(
+            settings.input_map.bindings(Action::Thrust),
+            [
+                Binding::Key(KeyCode::ArrowUp),
+                Binding::Gamepad(GamepadButton::DPadUp),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_newer_versions() {
+        let contents = format!("(version: {})", Settings::VERSION + 1);
+        assert!(storage::parse::<Settings>(&contents).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_settings() {
+        assert!(storage::parse::<Settings>("(volume: \"loud\")").is_err());
+    }
+}
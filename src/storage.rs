@@ -0,0 +1,133 @@
+//! Reading and writing small text files that persist between launches, like settings and player
+//! progress.
+//!
+//! Native builds keep each file as `<name>.ron` in the platform config directory, web builds keep
+//! it in the browser's local storage under `mine_x3.<name>`.
+//!
+//! Files are saved in a versioned format, see [`Versioned`]. A file that can't be loaded is backed
+//! up before it's ignored, so it isn't lost when it's next saved over.
+
+use bevy::log::{error, warn};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Something saved with a `version` field, so older saves can be brought up to date.
+pub trait Versioned: Serialize + Sized {
+    /// The current format version. Bump this when a field changes meaning, and migrate older
+    /// versions in [`Versioned::parse`]. Adding a field doesn't need a new version.
+    const VERSION: u32;
+
+    /// Parse `contents` saved as `version`, which is never newer than [`Versioned::VERSION`].
+    fn parse(version: u32, contents: &str) -> Result<Self, String>;
+}
+
+/// Load what's saved under `name`, or `None` if nothing is or it can't be loaded.
+pub fn load<T: Versioned>(name: &str) -> Option<T> {
+    let contents = read(name)?;
+    match parse(&contents) {
+        Ok(data) => Some(data),
+        Err(error) => {
+            warn!("Couldn't load `{name}`, ignoring it: {error}");
+            back_up(name, &contents);
+            None
+        }
+    }
+}
+
+/// Parse versioned `contents`, rejecting versions newer than this build understands.
+pub fn parse<T: Versioned>(contents: &str) -> Result<T, String> {
+    #[derive(Deserialize)]
+    struct Header {
+        #[serde(default)]
+        version: u32,
+    }
+
+    let Header { version } = from_ron(contents)?;
+    if version > T::VERSION {
+        return Err(format!(
+            "it's version {version}, but this build only supports up to version {}",
+            T::VERSION
+        ));
+    }
+    T::parse(version, contents)
+}
+
+/// Parse `contents` as RON.
+pub fn from_ron<T: DeserializeOwned>(contents: &str) -> Result<T, String> {
+    ron::from_str(contents).map_err(|error| error.to_string())
+}
+
+/// Save `data` under `name`, logging any error.
+pub fn save<T: Versioned>(name: &str, data: &T) {
+    let contents = match ron::ser::to_string_pretty(data, Default::default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            error!("Couldn't serialize `{name}`: {error}");
+            return;
+        }
+    };
+
+    if let Err(error) = write(name, &contents) {
+        error!("Couldn't save `{name}`: {error}");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod platform {
+    use std::{fs, path::PathBuf};
+
+    fn path(name: &str) -> Option<PathBuf> {
+        Some(
+            dirs::config_dir()?
+                .join("mine_x3")
+                .join(format!("{name}.ron")),
+        )
+    }
+
+    pub fn read(name: &str) -> Option<String> {
+        fs::read_to_string(path(name)?).ok()
+    }
+
+    pub fn write(name: &str, contents: &str) -> Result<(), String> {
+        let path = path(name).ok_or("no config directory on this platform")?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+        }
+        fs::write(path, contents).map_err(|error| error.to_string())
+    }
+
+    pub fn back_up(name: &str, contents: &str) {
+        if let Some(path) = path(name) {
+            let _ = fs::write(path.with_extension("ron.bak"), contents);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    fn key(name: &str) -> String {
+        format!("mine_x3.{name}")
+    }
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read(name: &str) -> Option<String> {
+        local_storage()?.get_item(&key(name)).ok()?
+    }
+
+    pub fn write(name: &str, contents: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or("local storage is unavailable")?
+            .set_item(&key(name), contents)
+            .map_err(|error| format!("{error:?}"))
+    }
+
+    pub fn back_up(name: &str, contents: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(&format!("{}.bak", key(name)), contents);
+        }
+    }
+}
+
+pub use platform::{back_up, read, write};
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect};
+use crate::{asset_tracking::LoadResource, audio::ui_sound_effect, theme::navigation::UiFocus};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
@@ -10,32 +10,49 @@ pub(super) fn plugin(app: &mut App) {
     app.load_resource::<InteractionAssets>();
     app.add_observer(play_on_hover_sound_effect);
     app.add_observer(play_on_click_sound_effect);
+    app.add_systems(
+        Update,
+        play_on_focus_sound_effect.run_if(resource_changed::<UiFocus>),
+    );
 }
 
 /// Palette for widget interactions. Add this to an entity that supports
 /// [`Interaction`]s, such as a button, to change its [`BackgroundColor`] based
-/// on the current interaction state.
+/// on the current interaction state and whether it has [`UiFocus`].
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
 pub struct InteractionPalette {
     pub none: Color,
     pub hovered: Color,
+    pub focused: Color,
     pub pressed: Color,
 }
 
+impl InteractionPalette {
+    /// Pointer interactions take priority over focus.
+    fn color(&self, interaction: Interaction, focused: bool) -> Color {
+        match interaction {
+            Interaction::None if focused => self.focused,
+            Interaction::None => self.none,
+            Interaction::Hovered => self.hovered,
+            Interaction::Pressed => self.pressed,
+        }
+    }
+}
+
 fn apply_interaction_palette(
-    mut palette_query: Query<
-        (&Interaction, &InteractionPalette, &mut BackgroundColor),
-        Changed<Interaction>,
-    >,
+    focus: Res<UiFocus>,
+    mut palette_query: Query<(
+        Entity,
+        Ref<Interaction>,
+        &InteractionPalette,
+        &mut BackgroundColor,
+    )>,
 ) {
-    for (interaction, palette, mut background) in &mut palette_query {
-        *background = match interaction {
-            Interaction::None => palette.none,
-            Interaction::Hovered => palette.hovered,
-            Interaction::Pressed => palette.pressed,
+    for (entity, interaction, palette, mut background) in &mut palette_query {
+        if interaction.is_changed() || focus.is_changed() {
+            *background = palette.color(*interaction, focus.0 == Some(entity)).into();
         }
-        .into();
     }
 }
 
@@ -69,7 +86,7 @@ fn play_on_hover_sound_effect(
     };
 
     if interaction_query.contains(pointer.entity) {
-        commands.spawn(sound_effect(interaction_assets.hover.clone()));
+        commands.spawn(ui_sound_effect(interaction_assets.hover.clone()));
     }
 }
 
@@ -84,6 +101,20 @@ fn play_on_click_sound_effect(
     };
 
     if interaction_query.contains(pointer.entity) {
-        commands.spawn(sound_effect(interaction_assets.click.clone()));
+        commands.spawn(ui_sound_effect(interaction_assets.click.clone()));
+    }
+}
+
+fn play_on_focus_sound_effect(
+    mut commands: Commands,
+    focus: Res<UiFocus>,
+    interaction_assets: Option<Res<InteractionAssets>>,
+) {
+    let Some(interaction_assets) = interaction_assets else {
+        return;
+    };
+
+    if focus.0.is_some() {
+        commands.spawn(ui_sound_effect(interaction_assets.hover.clone()));
     }
 }
@@ -0,0 +1,257 @@
+//! Keyboard and gamepad focus navigation between the buttons of a [`UiRoot`].
+//!
+//! Tab / Shift+Tab step through buttons in reading order, while the arrow keys, D-pad and left
+//! stick move focus to the nearest button in that direction. Enter or the gamepad's south button
+//! activates the focused button by sending it the same [`Pointer<Click>`] a mouse would.
+
+use std::time::Duration;
+
+use bevy::{
+    camera::NormalizedRenderTarget,
+    ecs::system::SystemParam,
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+};
+
+use crate::{
+    AppSystems,
+    theme::{interaction::InteractionPalette, widget::UiRoot},
+};
+
+/// How far a stick has to be pushed to move focus.
+const STICK_NAVIGATION_THRESHOLD: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<UiRoot>();
+    app.init_resource::<UiFocus>();
+    app.init_resource::<UiFocusLocked>();
+    app.add_systems(
+        Update,
+        (
+            clear_stale_focus,
+            (navigate_focus, activate_focus).run_if(not(is_focus_locked)),
+        )
+            .chain()
+            // record the input before menus react to it, so a menu can lock focus in time
+            .in_set(AppSystems::RecordInput),
+    );
+}
+
+/// The button that has keyboard / gamepad focus, if any.
+#[derive(Resource, Debug, Default)]
+pub struct UiFocus(pub Option<Entity>);
+
+/// While set, focus doesn't move and the focused button can't be activated, so a menu can read
+/// the same keys for something else, like waiting for a key to bind.
+#[derive(Resource, Debug, Default, PartialEq, Eq)]
+pub struct UiFocusLocked(pub bool);
+
+fn is_focus_locked(focus_locked: Res<UiFocusLocked>) -> bool {
+    focus_locked.0
+}
+
+/// Buttons that can receive focus.
+type Focusable = (With<Button>, With<InteractionPalette>);
+
+/// How the player asked to move focus this frame.
+#[derive(Debug, Clone, Copy)]
+enum FocusMove {
+    Next,
+    Previous,
+    Direction(Vec2),
+}
+
+fn clear_stale_focus(mut focus: ResMut<UiFocus>, targets: FocusTargets) {
+    if let Some(entity) = focus.0
+        && !targets.buttons.contains(entity)
+    {
+        focus.0 = None;
+    }
+}
+
+fn navigate_focus(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut stick_held: Local<bool>,
+    mut focus: ResMut<UiFocus>,
+    targets: FocusTargets,
+) {
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let mut focus_move = if keys.just_pressed(KeyCode::Tab) {
+        Some(if shift {
+            FocusMove::Previous
+        } else {
+            FocusMove::Next
+        })
+    } else if keys.just_pressed(KeyCode::ArrowUp) {
+        Some(FocusMove::Direction(Vec2::NEG_Y))
+    } else if keys.just_pressed(KeyCode::ArrowDown) {
+        Some(FocusMove::Direction(Vec2::Y))
+    } else if keys.just_pressed(KeyCode::ArrowLeft) {
+        Some(FocusMove::Direction(Vec2::NEG_X))
+    } else if keys.just_pressed(KeyCode::ArrowRight) {
+        Some(FocusMove::Direction(Vec2::X))
+    } else {
+        None
+    };
+
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::DPadUp) {
+            focus_move = Some(FocusMove::Direction(Vec2::NEG_Y));
+        } else if gamepad.just_pressed(GamepadButton::DPadDown) {
+            focus_move = Some(FocusMove::Direction(Vec2::Y));
+        } else if gamepad.just_pressed(GamepadButton::DPadLeft) {
+            focus_move = Some(FocusMove::Direction(Vec2::NEG_X));
+        } else if gamepad.just_pressed(GamepadButton::DPadRight) {
+            focus_move = Some(FocusMove::Direction(Vec2::X));
+        }
+    }
+
+    // only move once per stick push, UI space has +Y pointing down
+    let stick = gamepads
+        .iter()
+        .map(Gamepad::left_stick)
+        .find(|stick| stick.length() > STICK_NAVIGATION_THRESHOLD);
+    match stick {
+        Some(stick) if !*stick_held => {
+            *stick_held = true;
+            focus_move = Some(FocusMove::Direction(if stick.x.abs() > stick.y.abs() {
+                Vec2::new(stick.x.signum(), 0.0)
+            } else {
+                Vec2::new(0.0, -stick.y.signum())
+            }));
+        }
+        Some(_) => {}
+        None => *stick_held = false,
+    }
+
+    let Some(focus_move) = focus_move else {
+        return;
+    };
+
+    let next = targets.next_focus(focus.0, focus_move);
+    if next.is_some() && next != focus.0 {
+        focus.0 = next;
+    }
+}
+
+/// Press the focused button with Enter or the gamepad's south button (A on Xbox controllers).
+fn activate_focus(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focus: Res<UiFocus>,
+) {
+    let Some(entity) = focus.0 else {
+        return;
+    };
+
+    let activated = keys.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+    if activated {
+        commands.trigger(synthetic_click(entity));
+    }
+}
+
+/// A click on `entity` for activating buttons without a pointer, so they run their usual observer.
+fn synthetic_click(entity: Entity) -> Pointer<Click> {
+    Pointer::new(
+        // pretend to be the mouse, the location isn't used by button observers
+        PointerId::Mouse,
+        Location {
+            target: NormalizedRenderTarget::None {
+                width: 0,
+                height: 0,
+            },
+            position: Vec2::ZERO,
+        },
+        Click {
+            button: PointerButton::Primary,
+            hit: HitData::new(Entity::PLACEHOLDER, 0.0, None, None),
+            duration: Duration::ZERO,
+        },
+        entity,
+    )
+}
+
+/// The focusable buttons on screen and the [`UiRoot`]s they belong to.
+#[derive(SystemParam)]
+struct FocusTargets<'w, 's> {
+    buttons: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static UiGlobalTransform,
+            &'static InheritedVisibility,
+        ),
+        Focusable,
+    >,
+    parents: Query<'w, 's, &'static ChildOf>,
+    roots: Query<'w, 's, Option<&'static GlobalZIndex>, With<UiRoot>>,
+}
+
+impl FocusTargets<'_, '_> {
+    fn root_of(&self, entity: Entity) -> Option<Entity> {
+        self.parents
+            .iter_ancestors(entity)
+            .find(|ancestor| self.roots.contains(*ancestor))
+    }
+
+    /// Visible buttons sharing a root with `focus`, in reading order. With nothing focused,
+    /// this is the buttons of the topmost root.
+    fn candidates(&self, focus: Option<Entity>) -> Vec<(Entity, Vec2)> {
+        let visible = || {
+            self.buttons
+                .iter()
+                .filter(|(_, _, visibility)| visibility.get())
+                .map(|(entity, transform, _)| (entity, transform.translation))
+        };
+
+        let root = focus.and_then(|entity| self.root_of(entity)).or_else(|| {
+            visible()
+                .filter_map(|(entity, _)| self.root_of(entity))
+                .max_by_key(|root| self.roots.get(*root).ok().flatten().map_or(0, |z| z.0))
+        });
+
+        let mut candidates: Vec<_> = visible()
+            .filter(|(entity, _)| self.root_of(*entity) == root)
+            .collect();
+        candidates.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        candidates
+    }
+
+    /// The button to focus after moving from `focus`. With nothing focused yet, any move
+    /// focuses the first button.
+    fn next_focus(&self, focus: Option<Entity>, focus_move: FocusMove) -> Option<Entity> {
+        let candidates = self.candidates(focus);
+        let Some(index) =
+            focus.and_then(|focus| candidates.iter().position(|(entity, _)| *entity == focus))
+        else {
+            return candidates.first().map(|(entity, _)| *entity);
+        };
+        let origin = candidates[index].1;
+
+        match focus_move {
+            FocusMove::Next => Some(candidates[(index + 1) % candidates.len()].0),
+            FocusMove::Previous => {
+                Some(candidates[(index + candidates.len() - 1) % candidates.len()].0)
+            }
+            // prefer buttons straight ahead over ones off to the side
+            FocusMove::Direction(direction) => candidates
+                .iter()
+                .filter_map(|(entity, position)| {
+                    let offset = *position - origin;
+                    let along = offset.dot(direction);
+                    (along > 0.0).then(|| (*entity, along + 2.0 * offset.perp_dot(direction).abs()))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(entity, _)| entity),
+        }
+    }
+}
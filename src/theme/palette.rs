@@ -6,11 +6,24 @@ pub const LABEL_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
 /// #fcfbcc
 pub const HEADER_TEXT: Color = Color::srgb(0.988, 0.984, 0.800);
 
+/// #808080
+pub const LOCKED_TEXT: Color = Color::srgb(0.502, 0.502, 0.502);
+/// #74cc6d
+pub const PURCHASED_TEXT: Color = Color::srgb(0.455, 0.800, 0.427);
+
 /// #ececec
 pub const BUTTON_TEXT: Color = Color::srgb(0.925, 0.925, 0.925);
 /// #4666bf
 pub const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
 /// #6299d1
 pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
+/// #5580c8
+pub const BUTTON_FOCUSED_BACKGROUND: Color = Color::srgb(0.333, 0.502, 0.784);
 /// #3d4999
 pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
+
+/// #1c1f33
+pub const PROGRESS_BAR_BACKGROUND: Color = Color::srgb(0.110, 0.122, 0.200);
+
+/// #23284a
+pub const PANEL_BACKGROUND: Color = Color::srgb(0.137, 0.157, 0.290);
@@ -10,10 +10,21 @@ use bevy::{
 
 use crate::theme::{interaction::InteractionPalette, palette::*};
 
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ProgressBar>();
+    app.add_systems(Update, update_progress_bars);
+}
+
+/// Marks the root of a menu. Keyboard and gamepad focus stays within a single root.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct UiRoot;
+
 /// A root UI node that fills the window and centers its content.
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
     (
         Name::new(name),
+        UiRoot,
         Node {
             position_type: PositionType::Absolute,
             width: Percent(100.0),
@@ -58,6 +69,7 @@ where
 {
     button_base(
         text,
+        40.0,
         action,
         (
             Node {
@@ -72,6 +84,31 @@ where
     )
 }
 
+/// A medium rounded button with text and an action defined as an [`Observer`].
+/// Fits in a row of a settings grid.
+pub fn button_medium<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+where
+    E: EntityEvent,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    button_base(
+        text,
+        24.0,
+        action,
+        (
+            Node {
+                width: Px(220.0),
+                height: Px(36.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BorderRadius::all(Val::Px(12.0)),
+        ),
+    )
+}
+
 /// A small square button with text and an action defined as an [`Observer`].
 pub fn button_small<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
@@ -81,6 +118,7 @@ where
 {
     button_base(
         text,
+        40.0,
         action,
         Node {
             width: Px(30.0),
@@ -95,6 +133,7 @@ where
 /// A simple button with text and an action defined as an [`Observer`]. The button's layout is provided by `button_bundle`.
 fn button_base<E, B, M, I>(
     text: impl Into<String>,
+    font_size: f32,
     action: I,
     button_bundle: impl Bundle,
 ) -> impl Bundle
@@ -117,12 +156,13 @@ where
                     InteractionPalette {
                         none: BUTTON_BACKGROUND,
                         hovered: BUTTON_HOVERED_BACKGROUND,
+                        focused: BUTTON_FOCUSED_BACKGROUND,
                         pressed: BUTTON_PRESSED_BACKGROUND,
                     },
                     children![(
                         Name::new("Button Text"),
                         Text(text),
-                        TextFont::from_font_size(40.0),
+                        TextFont::from_font_size(font_size),
                         TextColor(BUTTON_TEXT),
                         // Don't bubble picking events from the text up to the button.
                         Pickable::IGNORE,
@@ -133,3 +173,49 @@ where
         })),
     )
 }
+
+/// How full a [`progress_bar`] is, from 0 to 1.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ProgressBar(pub f32);
+
+#[derive(Component)]
+struct ProgressBarFill;
+
+/// A horizontal bar filled with `color` up to its [`ProgressBar`] fraction.
+pub fn progress_bar(width: f32, color: Color) -> impl Bundle {
+    (
+        Name::new("Progress Bar"),
+        ProgressBar(1.0),
+        Node {
+            width: Px(width),
+            height: Px(14.0),
+            ..default()
+        },
+        BackgroundColor(PROGRESS_BAR_BACKGROUND),
+        BorderRadius::all(Px(4.0)),
+        children![(
+            Name::new("Progress Bar Fill"),
+            ProgressBarFill,
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(color),
+            BorderRadius::all(Px(4.0)),
+        )],
+    )
+}
+
+fn update_progress_bars(
+    bars: Query<(&ProgressBar, &Children), Changed<ProgressBar>>,
+    mut fills: Query<&mut Node, With<ProgressBarFill>>,
+) {
+    for (bar, children) in &bars {
+        let mut fills = fills.iter_many_mut(children);
+        while let Some(mut fill) = fills.fetch_next() {
+            fill.width = Percent(100.0 * bar.0.clamp(0.0, 1.0));
+        }
+    }
+}